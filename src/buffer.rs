@@ -16,11 +16,20 @@
 
 use std::fmt::{Display, Formatter};
 use log::trace;
-use std::mem::size_of;
 use colored::{Colorize, Color};
 use rand::prelude::SliceRandom;
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{Zero, One, Signed, ToPrimitive};
+use std::cmp::Ordering;
+use crate::float::{FloatFormat, FloatLiteral};
+use crate::fixed::FixedFormat;
+use crate::checksum::CrcModel;
+use crate::ecc::{HammingCode, Decoded};
+use crate::text::TextEncoding;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum BitIndex {
     HighestBit,
     LowestBit,
@@ -33,12 +42,149 @@ pub struct BitsIndexRange(pub BitIndex, pub BitIndex);
 #[derive(Debug, Copy, Clone)]
 pub enum BincBufferType {
     Integer,
-    Float,
-    Fixed,
+    Float(FloatFormat),
+    Fixed(FixedFormat),
 }
 
-// TODO user struct BitUint from crate 'num-bigint'
-type BufferType = u128;
+pub type BufferType = BigUint;
+
+/// The widest buffer binc can work with.
+pub const MAX_EFFECTIVE_BITS: usize = 4096;
+
+/// How many bits are printed in one row of the bit ruler.
+const BITS_PER_ROW: usize = 128;
+
+/// Status of the last arithmetic or bitwise operation, like a status register of a CPU has.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Flags {
+    /// carry out of the highest bit of the range, or borrow for subtraction
+    pub carry: bool,
+    /// the result does not fit the range as a signed number
+    pub overflow: bool,
+    pub zero: bool,
+    /// the highest bit of the result
+    pub sign: bool,
+    /// the result has an even number of ones
+    pub parity: bool,
+}
+
+/// How division of signed integers rounds the quotient.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Division {
+    /// toward zero, as C and Rust do, the remainder has the sign of the dividend
+    Truncated,
+    /// toward negative infinity, as Python does, the remainder has the sign of the divisor
+    Floored,
+}
+
+/// What `+ - * pow <<` do with a result, which does not fit the range.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OverflowMode {
+    /// the lowest bits of the result are kept
+    Wrap,
+    /// the result is clamped to the lowest or the highest number of the range, as DSP instructions do
+    Saturate,
+    /// the operation fails, the buffer does not change
+    Checked,
+}
+
+/// How the bits of an integer stand for its value, `signed` and `unsigned` buffers are two's complement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Representation {
+    TwosComplement,
+    /// a negative number has all bits of its magnitude inverted, zero has two forms
+    OnesComplement,
+    /// the highest bit is the sign, the rest is the magnitude, zero has two forms
+    SignMagnitude,
+    /// offset binary: the bits are the value plus K, which is 2^(n-1) by default
+    Excess(Option<BufferType>),
+    /// base -2, every odd bit has a negative weight
+    Negabinary,
+}
+
+impl Representation {
+    pub fn name(&self, width: usize) -> String {
+        match self {
+            Representation::TwosComplement => "two's complement".to_owned(),
+            Representation::OnesComplement => "ones' complement".to_owned(),
+            Representation::SignMagnitude => "sign-magnitude".to_owned(),
+            Representation::Excess(_) => format!("excess-{}", self.excess(width)),
+            Representation::Negabinary => "negabinary".to_owned(),
+        }
+    }
+
+    fn excess(&self, width: usize) -> BigInt {
+        match self {
+            Representation::Excess(Some(excess)) => BigInt::from(excess.clone()),
+            _ => BigInt::from(mask_nth_bit(width - 1)),
+        }
+    }
+
+    /// Value of the bits of the width.
+    fn decode(&self, bits: &BufferType, width: usize) -> BigInt {
+        let sign = bits.bit(width as u64 - 1);
+        match self {
+            Representation::TwosComplement => signed_value(bits, width),
+            Representation::OnesComplement if sign => -BigInt::from(bits ^ mask_n_ones_from_right(width)),
+            Representation::SignMagnitude if sign => -BigInt::from(bits & mask_n_ones_from_right(width - 1)),
+            Representation::OnesComplement | Representation::SignMagnitude => BigInt::from(bits.clone()),
+            Representation::Excess(_) => BigInt::from(bits.clone()) - self.excess(width),
+            Representation::Negabinary => {
+                let odd_bits = BigInt::from(negabinary_mask(width));
+                (BigInt::from(bits.clone()) ^ &odd_bits) - odd_bits
+            }
+        }
+    }
+
+    /// Bits of the value, the value, which the width cannot hold, is an error.
+    fn encode(&self, value: &BigInt, width: usize) -> Result<BufferType, String> {
+        let max_magnitude = BigInt::from(mask_n_ones_from_right(width - 1));
+        let bits = match self {
+            Representation::TwosComplement => return Ok(signed_bits(value, width)),
+            Representation::OnesComplement | Representation::SignMagnitude if value.magnitude() > max_magnitude.magnitude() =>
+                return Err(format!("The number does not fit {} bits of {}, it must be from -{} to {}", width, self.name(width), max_magnitude, max_magnitude)),
+            Representation::OnesComplement if value.is_negative() => value.magnitude() ^ mask_n_ones_from_right(width),
+            Representation::SignMagnitude if value.is_negative() => value.magnitude() | mask_nth_bit(width - 1),
+            Representation::OnesComplement | Representation::SignMagnitude => value.magnitude().clone(),
+            Representation::Excess(_) => (value + self.excess(width)).to_biguint().unwrap_or_else(|| mask_nth_bit(width)),
+            Representation::Negabinary => {
+                let odd_bits = negabinary_mask(width);
+                (value + BigInt::from(odd_bits.clone())).to_biguint().map(|bits| bits ^ odd_bits).unwrap_or_else(|| mask_nth_bit(width))
+            }
+        };
+        if bits.bits() as usize > width {
+            return Err(format!(
+                "The number does not fit {} bits of {}, it must be from {} to {}",
+                width, self.name(width), self.decode(&self.lowest_bits(width), width), self.decode(&self.highest_bits(width), width)
+            ));
+        }
+        Ok(bits)
+    }
+
+    /// Bits of the lowest number of the width.
+    fn lowest_bits(&self, width: usize) -> BufferType {
+        match self {
+            Representation::Negabinary => negabinary_mask(width),
+            _ => BufferType::zero()
+        }
+    }
+
+    /// Bits of the highest number of the width.
+    fn highest_bits(&self, width: usize) -> BufferType {
+        match self {
+            Representation::Negabinary => negabinary_mask(width) ^ mask_n_ones_from_right(width),
+            _ => mask_n_ones_from_right(width)
+        }
+    }
+
+    /// The sign bit is set, it is so for the negative zero too.
+    fn is_negative(&self, bits: &BufferType, width: usize) -> bool {
+        match self {
+            Representation::OnesComplement | Representation::SignMagnitude => bits.bit(width as u64 - 1),
+            _ => self.decode(bits, width).is_negative()
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct BincBuffer {
@@ -46,7 +192,17 @@ pub struct BincBuffer {
     effective_bits: usize,
     buffer_type: BincBufferType,
     is_signed: bool,
-    carry: bool,
+    flags: Flags,
+    division: Division,
+    overflow_mode: OverflowMode,
+    modulus: Option<BufferType>,
+    field: Option<BufferType>, // reduction polynomial of GF(2^n) arithmetics
+    crc: CrcModel,
+    representation: Representation,
+    text_encoding: Option<TextEncoding>, // the code point of the low 32 bits is shown if it is not chosen
+    is_text: bool, // bytes of a string literal, which widen the buffer they are assigned to
+    is_float_literal: bool, // the integer part of a floating point literal, which must fit the range instead of wrapping
+    named_ranges: Vec<(String, BitsIndexRange)>, // fields, which `let` has named, in the order they were named
 }
 
 // impl Binary for BinaryStore {
@@ -67,7 +223,7 @@ pub struct BincBuffer {
 //     }
 // }
 
-/// implementation for Booth's algorithm
+// implementation for Booth's algorithm
 // fn multiply(ref mut dest: &mut Vec<u8>, ref source: Vec<u8>) {
 //     -- not ready
 //     for i in 0..dest.len() {
@@ -106,201 +262,1033 @@ pub struct BincBuffer {
 impl BincBuffer {
 
     pub fn new(buffer_type: BincBufferType, is_signed: bool, size: usize) -> Result<Self, String> {
-        match next_power_of_two_rounded_up(size) {
-            Ok(size) =>
-                Ok(Self {
-                    buffer: 0u128,
-                    effective_bits: size,
-                    buffer_type,
-                    is_signed,
-                    carry: false,
-                }),
-            Err(message) => Err(message)
-        }
+        check_width(size)?;
+        Ok(Self {
+            buffer: BufferType::zero(),
+            effective_bits: size,
+            buffer_type,
+            is_signed,
+            flags: Flags::default(),
+            division: Division::Truncated,
+            overflow_mode: OverflowMode::Wrap,
+            modulus: None,
+            field: None,
+            crc: CrcModel::CRC32_ISO_HDLC,
+            representation: Representation::TwosComplement,
+            text_encoding: None,
+            is_text: false,
+            is_float_literal: false,
+            named_ranges: Vec::new(),
+        })
     }
 
     pub fn from_char(c: char) -> Result<Self, String> {
-        Ok(
-            Self {
-                buffer: c.into(),
-                effective_bits: c.len_utf8() * 8,
-                buffer_type: BincBufferType::Integer,
-                is_signed: false,
-                carry: false,
-            }
-        )
+        // the code point in whole bytes, its encodings are views of the buffer
+        let effective_bits = ((u32::BITS - (c as u32).leading_zeros()) as usize).max(1).div_ceil(8) * 8;
+        Ok(Self { buffer: BufferType::from(c as u32), ..Self::new(BincBufferType::Integer, false, effective_bits)? })
+    }
+
+    /// Bytes of a string literal, the first byte is the highest one.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        check_width(bytes.len() * 8)?;
+        let mut text = Self::new(BincBufferType::Integer, false, bytes.len() * 8)?;
+        text.buffer = BufferType::from_bytes_be(bytes);
+        text.is_text = true;
+        Ok(text)
+    }
+
+    /// The integer part of a floating point literal for an integer buffer.
+    pub fn from_float_literal(literal: &FloatLiteral) -> Result<Self, String> {
+        let mut number = Self::from_str(&literal.truncate()?, 10)?;
+        number.is_float_literal = true;
+        Ok(number)
     }
 
     pub fn from_str(number_literal: &str, radix: u32) -> Result<Self, String> {
-        let radix= radix as u128;
         trace!("Number::from: parsing literal '{}', radix {}", number_literal, radix);
-        let is_negative = number_literal.starts_with("-");
+        let is_negative = number_literal.starts_with('-');
 
-        // TODO floating, fixed
         let mut it = number_literal.chars();
         if is_negative {
             it.next();
         }
 
-        // TODO use u128::from_str_radix() or BigUint::from_str_radix(...)
-        let mut buffer = 0u128;
-        while let Some(c) = it.next() {
+        let mut buffer = BufferType::zero();
+        for c in it {
             let n = match c {
                 '0'..='9' => c as u32 - '0' as u32,
                 'a'..='z' => c as u32 - 'a' as u32 + 10,
                 'A'..='Z' => c as u32 - 'A' as u32 + 10,
-                _ => return Err(format!("letter {} cannot represent a digit", c).to_owned())
-            } as u128;
+                _ => return Err(format!("letter {} cannot represent a digit", c))
+            };
             if n < radix {
                 buffer *= radix;
                 buffer += n;
             } else {
-                return Err(format!("Letter '{}' cannot be used for number notation in base {}", c, radix).to_owned());
+                return Err(format!("Letter '{}' cannot be used for number notation in base {}", c, radix));
             }
         }
         trace!("Number::from: parsed buffer 0b{:b}", buffer);
 
-        let buffer_length = size_of::<BufferType>() * 8;
-        let length_in_bits = match next_power_of_two_rounded_up(buffer_length - buffer.leading_zeros() as usize) {
-            Ok(s) => s,
-            Err(m) => return Err(m)
-        };
+        // -m takes the bits of m - 1 and the sign bit
+        let value_bits = if is_negative && !buffer.is_zero() { (&buffer - 1u32).bits() as usize + 1 } else { buffer.bits() as usize };
+        let length_in_bits = next_power_of_two_rounded_up(value_bits)?;
 
-        if is_negative && buffer != 0 {
+        if is_negative && !buffer.is_zero() {
             trace!("Number::from: negate {}", buffer);
-            buffer = !buffer;
-            buffer += 1;
+            buffer = mask_nth_bit(length_in_bits) - buffer;
+        }
+
+        Ok(Self { buffer: buffer & mask_n_ones_from_right(length_in_bits), ..Self::new(BincBufferType::Integer, is_negative, length_in_bits)? })
+    }
+
+    /// Floating point number already encoded to the format.
+    pub fn from_float(bits: u64, format: FloatFormat) -> Result<Self, String> {
+        Ok(Self { buffer: BufferType::from(bits), ..Self::new(BincBufferType::Float(format), true, format.width())? })
+    }
+
+    /// Fixed point number already quantized to the format.
+    pub fn from_fixed(bits: BufferType, format: FixedFormat) -> Result<Self, String> {
+        Ok(Self { buffer: bits, ..Self::new(BincBufferType::Fixed(format), format.signed, format.width())? })
+    }
+
+    fn with_range_do_arithmetics(&mut self, range: BitsIndexRange, arithmetic_operation: Box<dyn Fn(BufferType) -> BufferType>) {
+        let result = arithmetic_operation(self.get_bits(range));
+        self.set_range_result(range, result);
+    }
+
+    /// Sets the result of an operation to the range, then zero, sign and parity flags by the range,
+    /// carry and overflow are cleared, operations which produce them set them afterwards.
+    fn set_range_result(&mut self, range: BitsIndexRange, result: BufferType) {
+        self.set_bits(range, result);
+        let result = self.get_bits(range);
+        self.flags = Flags {
+            carry: false,
+            overflow: false,
+            zero: result.is_zero(),
+            sign: result.bit(self.range_width(range) as u64 - 1),
+            parity: result.count_ones().is_multiple_of(2),
+        };
+    }
+
+    /// The whole buffer and the operand are floating point numbers of the same format.
+    fn with_float_do_arithmetics(&mut self, format: FloatFormat, operand: BufferType, arithmetic_operation: Box<dyn Fn(f64, f64) -> f64>) {
+        let result = arithmetic_operation(format.decode(self.to_u64()), format.decode(operand.to_u64().unwrap_or(0)));
+        self.set_range_result(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::from(format.encode(result)));
+        // -0 is zero as well
+        self.flags.zero = result == 0f64;
+    }
+
+    /// Float format of the range, if the range is a floating point number - the whole buffer in float mode.
+    fn float_format_of(&self, range: BitsIndexRange) -> Option<FloatFormat> {
+        match self.buffer_type {
+            BincBufferType::Float(format) if self.range_width(range) == self.effective_bits => Some(format),
+            _ => None
         }
+    }
+
+    pub fn buffer_type(&self) -> BincBufferType {
+        self.buffer_type
+    }
+
+    pub fn float_format(&self) -> Option<FloatFormat> {
+        match self.buffer_type {
+            BincBufferType::Float(format) => Some(format),
+            _ => None
+        }
+    }
 
-        Ok(
-            Self {
-                buffer: buffer & mask_n_ones_from_right(length_in_bits),
-                effective_bits: length_in_bits,
-                buffer_type: if number_literal.contains(".") { BincBufferType::Float } else { BincBufferType::Integer },
-                is_signed: is_negative,
-                carry: false,
+    /// Fixed point format of the range, if the range is the whole buffer in fixed point mode.
+    fn fixed_format_of(&self, range: BitsIndexRange) -> Option<FixedFormat> {
+        match self.buffer_type {
+            BincBufferType::Fixed(format) if self.range_width(range) == self.effective_bits => Some(format),
+            _ => None
+        }
+    }
+
+    pub fn is_float_range(&self, range: BitsIndexRange) -> bool {
+        self.float_format_of(range).is_some()
+    }
+
+    /// Bits of the number to be the second operand of an arithmetic operation upon the range.
+    /// A floating point or a fixed point range takes the value of the number in its own format,
+    /// an integer range takes the integer part of a floating point or a fixed point number.
+    pub fn operand_for_range(&self, range: BitsIndexRange, mut operand: BincBuffer) -> Result<BufferType, String> {
+        if let Some(format) = self.float_format_of(range) {
+            return match operand.float_format() {
+                Some(operand_format) if format == operand_format => Ok(operand.buffer),
+                _ => Ok(BufferType::from(format.encode(operand.to_f64())))
+            };
+        }
+        if let Some(format) = self.fixed_format_of(range) {
+            return match operand.buffer_type {
+                BincBufferType::Fixed(operand_format) if format.width() == operand_format.width() && format.fraction_bits == operand_format.fraction_bits =>
+                    Ok(operand.buffer),
+                BincBufferType::Integer => {
+                    operand.signed_extend_to(self.max_size());
+                    Ok(format.scale_integer(&operand.buffer))
+                }
+                _ => Err(format!("The number cannot be an operand of {} number", format.name()))
+            };
+        }
+        match operand.buffer_type {
+            BincBufferType::Integer => {
+                self.check_float_literal_fits(range, &operand)?;
+                operand.signed_extend_to(self.max_size());
+                Ok(operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)))
+            }
+            _ => {
+                let value = operand.to_f64();
+                if !value.is_finite() {
+                    return Err("Infinity and NaN cannot be set to an integer".to_owned());
+                }
+                self.operand_for_range(range, BincBuffer::from_str(&format!("{:.0}", value.trunc()), 10)?)
             }
-        )
+        }
     }
 
-    fn with_range_do_arithmetics(&mut self, range: BitsIndexRange, arithmetic_operation: Box<dyn Fn(u128) -> u128>) {
-        let high_order_bit_index = self.resolve_bit_index(range.0);
-        let low_order_bit_index = self.resolve_bit_index(range.1);
-        self.carry = false; // TODO set carry in compliance to arithmetic operation
-        let mul = arithmetic_operation(self.get_bits(range));
-        self.buffer = self.buffer & !mask_from_bit_to_bit(high_order_bit_index, low_order_bit_index);
-        self.buffer = self.buffer | ((mul & mask_n_ones_from_right(high_order_bit_index - low_order_bit_index + 1)) << low_order_bit_index);
-        self.buffer = self.buffer & mask_n_ones_from_right(self.effective_bits);
+    /// Bits of an integer literal to be set to the range: two's complement bits as wide as the buffer,
+    /// or the value in the representation of an integer buffer.
+    pub fn integer_literal_for_range(&self, range: BitsIndexRange, mut literal: BincBuffer) -> Result<BufferType, String> {
+        self.check_float_literal_fits(range, &literal)?;
+        if self.representation == Representation::TwosComplement || !matches!(self.buffer_type, BincBufferType::Integer) {
+            literal.signed_extend_to(self.max_size());
+            return Ok(literal.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+        }
+        let value = if literal.is_negative() { signed_value(&literal.buffer, literal.effective_bits) } else { BigInt::from(literal.buffer) };
+        self.representation.encode(&value, self.range_width(range))
     }
 
-    pub fn range_add_bits(&mut self, range: BitsIndexRange, additive: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_add(additive)));
+    /// An integer literal wraps to the range, a floating point one is a value, which must fit it, as it must fit a fixed point range.
+    fn check_float_literal_fits(&self, range: BitsIndexRange, literal: &BincBuffer) -> Result<(), String> {
+        if !literal.is_float_literal {
+            return Ok(());
+        }
+        let width = self.range_width(range);
+        let value = if literal.is_negative() { signed_value(&literal.buffer, literal.effective_bits) } else { BigInt::from(literal.buffer.clone()) };
+        let (min, max) = self.range_bounds(width);
+        if value < min || value > max {
+            return Err(format!("The number is out of range of {} bits, it must be from {} to {}", width, min, max));
+        }
+        Ok(())
     }
 
-    pub fn range_subtract_bits(&mut self, range: BitsIndexRange, subtractive: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_sub(subtractive)));
+    /// Bits of a string literal, an integer buffer is widened to hold all of its bytes, a narrower range is an error.
+    pub fn text_literal_for_range(&mut self, range: BitsIndexRange, literal: BincBuffer) -> Result<BufferType, String> {
+        let is_whole = matches!(range, BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
+        if literal.effective_bits > self.range_width(range) {
+            if !is_whole || !matches!(self.buffer_type, BincBufferType::Integer) {
+                return Err(format!("The string takes {} bits, the range has {}", literal.effective_bits, self.range_width(range)));
+            }
+            self.convert(BincBufferType::Integer, self.is_signed, literal.effective_bits)?;
+        }
+        Ok(literal.buffer)
     }
 
-    pub fn range_multiply_bits(&mut self, range: BitsIndexRange, multiplayer: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_mul(multiplayer)));
+    pub fn is_text(&self) -> bool {
+        self.is_text
     }
 
-    pub fn range_div_bits(&mut self, range: BitsIndexRange, divisor: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_div(divisor)));
+    /// Bits of the source range to be the second operand of an arithmetic operation upon the target range.
+    /// If the target is a floating point or a fixed point number, but the source is only a part of it, the source is an integer.
+    pub fn range_operand_for_range(&self, range: BitsIndexRange, source_range: BitsIndexRange) -> BufferType {
+        let bits = self.get_bits(source_range);
+        match (self.float_format_of(range), self.float_format_of(source_range), self.fixed_format_of(range), self.fixed_format_of(source_range)) {
+            (Some(format), None, _, _) => BufferType::from(format.encode(bits.to_f64().unwrap_or(f64::INFINITY))),
+            (_, _, Some(format), None) => format.scale_integer(&bits),
+            _ => bits
+        }
     }
 
-    pub fn range_pow_bits(&mut self, range: BitsIndexRange, magnitude: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_pow(magnitude as u32)));
+    pub fn range_add_bits(&mut self, range: BitsIndexRange, additive: BufferType) -> Result<(), String> {
+        self.range_add_operand(range, additive, false)
     }
 
-    /// Find the number X, which powered to N is A.
-    /// x^n = a;
-    /// ln(x^n) = ln(a);
-    /// n*ln(x) = ln(a);
-    /// ln(x) = ln(a)/n;
-    /// exp(ln(x)) = exp(ln(a)/n);
-    /// x = exp(ln(a)/n);
-    pub fn range_root_bits(&mut self, range: BitsIndexRange, power: u128) {
+    /// Sum with the operand, which is a negative literal if `negative_operand` is set:
+    /// an unsigned buffer saturates or checks the sum with its signed value then.
+    pub fn range_add_operand(&mut self, range: BitsIndexRange, additive: BufferType, negative_operand: bool) -> Result<(), String> {
+        match self.float_format_of(range) {
+            Some(format) => self.with_float_do_arithmetics(format, additive, Box::new(|a, b| a + b)),
+            None => self.add_with_carry(range, additive, false, negative_operand)?
+        }
+        Ok(())
+    }
+
+    /// Integer sum of the range, the operand and the carry, carry out of the range is the new carry.
+    pub fn range_add_with_carry(&mut self, range: BitsIndexRange, additive: BufferType, carry: bool) -> Result<(), String> {
+        self.add_with_carry(range, additive, carry, false)
+    }
+
+    fn add_with_carry(&mut self, range: BitsIndexRange, additive: BufferType, carry: bool, negative_operand: bool) -> Result<(), String> {
+        if self.float_format_of(range).is_some() {
+            return Err("Add with carry works only with integers".to_owned());
+        }
+        let width = self.range_width(range);
+        let a = self.get_bits(range);
+        let b = additive & mask_n_ones_from_right(width);
+        let carry = BigInt::from(carry as u8);
+        let carry_out = (&a + &b + carry.magnitude()).bit(width as u64);
+        let overflow = !fits_signed(&(signed_value(&a, width) + signed_value(&b, width) + &carry), width);
+        let result = self.fit_to_range(range, self.range_value(&a, width) + self.operand_value(&b, width, negative_operand) + carry)?;
+        self.set_range_result(range, result);
+        self.flags.carry = carry_out;
+        self.flags.overflow = overflow;
+        Ok(())
+    }
+
+    pub fn range_subtract_bits(&mut self, range: BitsIndexRange, subtractive: BufferType) -> Result<(), String> {
+        self.range_subtract_operand(range, subtractive, false)
+    }
+
+    /// Difference with the operand, which is a negative literal if `negative_operand` is set, as `range_add_operand` has it.
+    pub fn range_subtract_operand(&mut self, range: BitsIndexRange, subtractive: BufferType, negative_operand: bool) -> Result<(), String> {
+        match self.float_format_of(range) {
+            Some(format) => self.with_float_do_arithmetics(format, subtractive, Box::new(|a, b| a - b)),
+            None => self.subtract_with_borrow(range, subtractive, false, negative_operand)?
+        }
+        Ok(())
+    }
+
+    /// Integer difference of the range, the operand and the borrow, which is the carry,
+    /// the new carry is set if the operand and the borrow are greater than the range.
+    pub fn range_subtract_with_borrow(&mut self, range: BitsIndexRange, subtractive: BufferType, borrow: bool) -> Result<(), String> {
+        self.subtract_with_borrow(range, subtractive, borrow, false)
+    }
+
+    fn subtract_with_borrow(&mut self, range: BitsIndexRange, subtractive: BufferType, borrow: bool, negative_operand: bool) -> Result<(), String> {
+        if self.float_format_of(range).is_some() {
+            return Err("Subtract with borrow works only with integers".to_owned());
+        }
+        let width = self.range_width(range);
+        let a = self.get_bits(range);
+        let b = subtractive & mask_n_ones_from_right(width);
+        let borrow = BigInt::from(borrow as u8);
+        let borrow_out = a < &b + borrow.magnitude();
+        let overflow = !fits_signed(&(signed_value(&a, width) - signed_value(&b, width) - &borrow), width);
+        let result = self.fit_to_range(range, self.range_value(&a, width) - self.operand_value(&b, width, negative_operand) - borrow)?;
+        self.set_range_result(range, result);
+        self.flags.carry = borrow_out;
+        self.flags.overflow = overflow;
+        Ok(())
+    }
+
+    pub fn range_multiply_bits(&mut self, range: BitsIndexRange, multiplayer: BufferType) -> Result<(), String> {
+        self.range_multiply_operand(range, multiplayer, false)
+    }
+
+    /// Product with the operand, which is a negative literal if `negative_operand` is set, as `range_add_operand` has it.
+    pub fn range_multiply_operand(&mut self, range: BitsIndexRange, multiplayer: BufferType, negative_operand: bool) -> Result<(), String> {
+        if let Some(polynomial) = self.field_of(range) {
+            return self.with_field_do_arithmetics(range, multiplayer, &polynomial, |a, b, polynomial| Ok(field_product(a, b, polynomial)));
+        }
+        match (self.float_format_of(range), self.fixed_format_of(range)) {
+            (Some(format), _) => self.with_float_do_arithmetics(format, multiplayer, Box::new(|a, b| a * b)),
+            (_, Some(format)) => {
+                let result = self.fit_to_range(range, format.scaled_product(&self.get_bits(range), &multiplayer))?;
+                self.set_range_result(range, result);
+            }
+            _ => {
+                let width = self.range_width(range);
+                let a = self.get_bits(range);
+                let b = multiplayer & mask_n_ones_from_right(width);
+                // the unsigned product does not fit the range, or the signed one does not
+                let carry = (&a * &b).bits() as usize > width;
+                let signed_product = signed_value(&a, width) * signed_value(&b, width);
+                let overflow = signed_product != signed_value(&(&a * &b), width);
+                let result = self.fit_to_range(range, self.range_value(&a, width) * self.operand_value(&b, width, negative_operand))?;
+                self.set_range_result(range, result);
+                self.flags.carry = carry;
+                self.flags.overflow = overflow;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn range_div_bits(&mut self, range: BitsIndexRange, divisor: BufferType) -> Result<(), String> {
+        if let Some(polynomial) = self.field_of(range) {
+            return self.with_field_do_arithmetics(range, divisor, &polynomial, |a, b, polynomial| {
+                // the divisor is an element of the field too, a multiple of the polynomial is zero
+                let divisor = polynomial_divide(b, polynomial).1;
+                if divisor.is_zero() {
+                    return Err("Cannot divide by 0".to_owned());
+                }
+                Ok(field_product(a, &field_inverse(&divisor, polynomial)?, polynomial))
+            });
+        }
+        match (self.float_format_of(range), self.fixed_format_of(range)) {
+            (Some(format), _) => self.with_float_do_arithmetics(format, divisor, Box::new(|a, b| a / b)),
+            (_, Some(format)) => self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| format.divide(&a, &divisor))),
+            _ if self.is_signed => self.range_signed_division(range, divisor, false),
+            _ => {
+                let divisor = divisor & mask_n_ones_from_right(self.range_width(range));
+                self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a / &divisor))
+            }
+        }
+        Ok(())
+    }
+
+    pub fn range_pow_bits(&mut self, range: BitsIndexRange, magnitude: BufferType) -> Result<(), String> {
+        if let Some(polynomial) = self.field_of(range) {
+            return self.with_field_do_arithmetics(range, magnitude, &polynomial, |a, exponent, polynomial| Ok(field_power(a, exponent, polynomial)));
+        }
+        let width = self.range_width(range);
+        if let Some(format) = self.float_format_of(range) {
+            self.with_float_do_arithmetics(format, magnitude, Box::new(|a, b| a.powf(b)));
+            return Ok(());
+        }
+        // the exponent of a fixed point range would be scaled as a value
+        self.check_integer_range(range, "Integer power")?;
+        if self.overflow_mode == OverflowMode::Wrap {
+            self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a.modpow(&magnitude, &mask_nth_bit(width))));
+        } else {
+            let power = bounded_power(&self.range_value(&self.get_bits(range), width), &magnitude, width);
+            let result = self.fit_to_range(range, power)?;
+            self.set_range_result(range, result);
+        }
+        Ok(())
+    }
+
+    /// Floating point root, or the exact integer root, rounded toward zero, at any width of the range.
+    pub fn range_root_bits(&mut self, range: BitsIndexRange, power: BufferType) -> Result<(), String> {
+        match self.float_format_of(range) {
+            Some(format) => self.with_float_do_arithmetics(format, power, Box::new(|a, n| if n == 2f64 { a.sqrt() } else { a.powf(n.recip()) })),
+            None => {
+                self.check_integer_range(range, "Integer root")?;
+                let width = self.range_width(range);
+                let power = match self.range_value(&power, width).to_u32() {
+                    Some(power) if power > 0 => power,
+                    _ => return Err("The degree of a root must be a positive number".to_owned())
+                };
+                let radicand = self.range_value(&self.get_bits(range), width);
+                if radicand.is_negative() && power % 2 == 0 {
+                    return Err("Even root of a negative number is not a real number".to_owned());
+                }
+                self.set_range_result(range, signed_bits(&radicand.nth_root(power), width));
+            }
+        }
+        Ok(())
+    }
+
+    /// Integer square root, rounded toward zero, a floating point number has `root` instead.
+    pub fn range_isqrt_bits(&mut self, range: BitsIndexRange) -> Result<(), String> {
+        self.check_integer_range(range, "Integer square root")?;
+        self.range_root_bits(range, BufferType::from(2u32))
+    }
+
+    /// Integer logarithm, the greatest power of the base, which is not greater than the range.
+    pub fn range_ilog_bits(&mut self, range: BitsIndexRange, base: BufferType) -> Result<(), String> {
+        self.check_integer_range(range, "Integer logarithm")?;
+        if base < BufferType::from(2u32) {
+            return Err("The base of a logarithm must be 2 or greater".to_owned());
+        }
+        let number = match self.range_value(&self.get_bits(range), self.range_width(range)).to_biguint() {
+            Some(number) if !number.is_zero() => number,
+            _ => return Err("Logarithm is defined only for positive numbers".to_owned())
+        };
+        let mut logarithm = 0u32;
+        let mut power = base.clone();
+        while power <= number {
+            power *= &base;
+            logarithm += 1;
+        }
+        self.set_range_result(range, BufferType::from(logarithm));
+        Ok(())
+    }
+
+    pub fn range_mod_bits(&mut self, range: BitsIndexRange, divisor: BufferType) {
+        match self.float_format_of(range) {
+            Some(format) => self.with_float_do_arithmetics(format, divisor, Box::new(|a, b| a % b)),
+            None if self.is_signed => self.range_signed_division(range, divisor, true),
+            None => {
+                let divisor = divisor & mask_n_ones_from_right(self.range_width(range));
+                self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a % &divisor))
+            }
+        }
+    }
+
+    /// Quotient or remainder of the range and the divisor as signed integers, rounded by the division mode.
+    fn range_signed_division(&mut self, range: BitsIndexRange, divisor: BufferType, is_remainder: bool) {
+        let width = self.range_width(range);
+        let (quotient, remainder) = self.divide_values(&signed_value(&self.get_bits(range), width), &signed_value(&divisor, width));
+        let result = if is_remainder { remainder } else { quotient };
+        let bits = signed_bits(&result, width);
+        // the lowest number divided by -1
+        let overflow = signed_value(&bits, width) != result;
+        self.set_range_result(range, bits);
+        self.flags.overflow = overflow;
+    }
+
+    /// Quotient and remainder rounded by the division mode.
+    fn divide_values(&self, dividend: &BigInt, divisor: &BigInt) -> (BigInt, BigInt) {
+        match self.division {
+            Division::Truncated => dividend.div_rem(divisor),
+            Division::Floored => dividend.div_mod_floor(divisor),
+        }
+    }
+
+    /// Integer division, which sets the quotient to the range and gives both the quotient and the remainder.
+    pub fn range_divmod(&mut self, range: BitsIndexRange, divisor: BufferType) -> Result<(BigInt, BigInt), String> {
+        self.check_integer_range(range, "Division with remainder")?;
+        let width = self.range_width(range);
+        let (quotient, remainder) = self.divide_values(&self.range_value(&self.get_bits(range), width), &self.range_value(&divisor, width));
+        let bits = signed_bits(&quotient, width);
+        let overflow = self.range_value(&bits, width) != quotient;
+        self.set_range_result(range, bits);
+        self.flags.overflow = overflow;
+        Ok((quotient, remainder))
+    }
+
+    /// Double width product of integers: the lowest half goes to the range and the highest half goes to the bits above it,
+    /// if the buffer has them, otherwise the highest half is returned, like x86 MUL puts it to EDX.
+    /// Carry and overflow are set if the highest half is significant.
+    pub fn range_multiply_wide(&mut self, range: BitsIndexRange, multiplier: BufferType) -> Result<Option<BufferType>, String> {
+        self.check_integer_range(range, "Widening multiplication")?;
+        let width = self.range_width(range);
+        let product = self.range_value(&self.get_bits(range), width) * self.range_value(&multiplier, width);
+        let is_significant = self.range_value(&signed_bits(&product, width), width) != product;
+        let high_half = self.set_wide_result(range, signed_bits(&product, 2 * width));
+        self.flags.carry = is_significant;
+        self.flags.overflow = is_significant;
+        Ok(high_half)
+    }
+
+    /// Carry-less product, as x86 PCLMULQDQ computes it; it is written like the product of `mulw`.
+    pub fn range_carryless_multiply(&mut self, range: BitsIndexRange, multiplier: BufferType) -> Result<Option<BufferType>, String> {
+        self.check_integer_range(range, "Carry-less multiplication")?;
+        let width = self.range_width(range);
+        let product = carryless_product(&self.get_bits(range), &(multiplier & mask_n_ones_from_right(width)));
+        Ok(self.set_wide_result(range, product))
+    }
+
+    /// Writes the double width product to the range and the bits above it, if the buffer has them,
+    /// otherwise writes the lowest half and returns the highest one.
+    fn set_wide_result(&mut self, range: BitsIndexRange, product: BufferType) -> Option<BufferType> {
+        let width = self.range_width(range);
+        let low = self.resolve_bit_index(range.1);
+        if low + 2 * width <= self.effective_bits {
+            self.set_range_result(BitsIndexRange(BitIndex::IndexedBit(low + 2 * width - 1), BitIndex::IndexedBit(low)), product);
+            None
+        } else {
+            let high_half = &product >> width;
+            self.set_range_result(range, product);
+            Some(high_half)
+        }
+    }
+
+    /// Operation upon the range and the operand as elements of GF(2^n), the range is reduced by the polynomial first.
+    fn with_field_do_arithmetics(&mut self, range: BitsIndexRange, operand: BufferType, polynomial: &BufferType,
+                                 operation: fn(&BufferType, &BufferType, &BufferType) -> Result<BufferType, String>) -> Result<(), String> {
+        let width = self.range_width(range);
+        let degree = polynomial.bits() as usize - 1;
+        if width < degree {
+            return Err(format!("GF(2^{}) needs a range of {} bits at least", degree, degree));
+        }
+        let element = polynomial_divide(&self.get_bits(range), polynomial).1;
+        let result = operation(&element, &(operand & mask_n_ones_from_right(width)), polynomial)?;
+        self.set_range_result(range, result);
+        Ok(())
+    }
+
+    /// Reduction polynomial of GF(2^n) arithmetics, which the integer buffer is in.
+    fn field_of(&self, range: BitsIndexRange) -> Option<BufferType> {
         match self.buffer_type {
-            BincBufferType::Integer => self.with_range_do_arithmetics(range, Box::new(move |a: u128| ((a as f64).ln() / power as f64).exp() as u128)),
-            _ => todo!("root operation for float and fixed is not implemented")
+            BincBufferType::Integer if !self.is_float_range(range) => self.field.clone(),
+            _ => None
         }
     }
 
-    pub fn range_mod_bits(&mut self, range: BitsIndexRange, divisor: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.wrapping_rem(divisor)));
+    /// Greatest common divisor of the range and the operand, it is never negative.
+    pub fn range_gcd(&mut self, range: BitsIndexRange, operand: BufferType) -> Result<(), String> {
+        self.check_integer_range(range, "Greatest common divisor")?;
+        let width = self.range_width(range);
+        let gcd = self.range_value(&self.get_bits(range), width).gcd(&self.range_value(&operand, width));
+        let result = self.fit_to_range(range, gcd)?;
+        self.set_range_result(range, result);
+        Ok(())
     }
 
-    pub fn range_xor_bits(&mut self, range: BitsIndexRange, second_operand: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a ^ second_operand));
+    /// Least common multiple of the range and the operand, it is never negative and may overflow the range.
+    pub fn range_lcm(&mut self, range: BitsIndexRange, operand: BufferType) -> Result<(), String> {
+        self.check_integer_range(range, "Least common multiple")?;
+        let width = self.range_width(range);
+        let lcm = self.range_value(&self.get_bits(range), width).lcm(&self.range_value(&operand, width));
+        let result = self.fit_to_range(range, lcm)?;
+        self.set_range_result(range, result);
+        Ok(())
     }
 
-    pub fn range_and_bits(&mut self, range: BitsIndexRange, second_operand: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a & second_operand));
+    /// Modular multiplicative inverse of the range, by the given modulus or by the one of `modulus` command.
+    pub fn range_modinv(&mut self, range: BitsIndexRange, modulus: Option<BufferType>) -> Result<(), String> {
+        self.check_integer_range(range, "Modular inverse")?;
+        let width = self.range_width(range);
+        let modulus = match modulus {
+            Some(modulus) => self.range_value(&modulus, width),
+            None => self.modulus_value()?
+        };
+        if !modulus.is_positive() {
+            return Err("The modulus must be a positive number".to_owned());
+        }
+        let inverse = modular_inverse(&self.range_value(&self.get_bits(range), width), &modulus)?;
+        let result = self.fit_to_range(range, inverse)?;
+        self.set_range_result(range, result);
+        Ok(())
+    }
+
+    /// The range raised to the power by the modulus of `modulus` command, a negative exponent raises the inverse.
+    pub fn range_modpow(&mut self, range: BitsIndexRange, exponent: BufferType) -> Result<(), String> {
+        self.check_integer_range(range, "Modular exponentiation")?;
+        let width = self.range_width(range);
+        let modulus = self.modulus_value()?;
+        let base = self.range_value(&self.get_bits(range), width);
+        let exponent = self.range_value(&exponent, width);
+        let base = if exponent.is_negative() { modular_inverse(&base, &modulus)? } else { base.mod_floor(&modulus) };
+        let result = self.fit_to_range(range, base.modpow(&exponent.abs(), &modulus))?;
+        self.set_range_result(range, result);
+        Ok(())
+    }
+
+    fn modulus_value(&self) -> Result<BigInt, String> {
+        match &self.modulus {
+            Some(modulus) => Ok(BigInt::from(modulus.clone())),
+            None => Err("No modulus is set, set it with `modulus M` first".to_owned())
+        }
+    }
+
+    fn check_integer_range(&self, range: BitsIndexRange, operation: &str) -> Result<(), String> {
+        if self.float_format_of(range).is_some() || self.fixed_format_of(range).is_some() {
+            return Err(format!("{} works only with integers", operation));
+        }
+        Ok(())
+    }
+
+    /// Value of the bits as the range holds them: signed or unsigned, as the buffer is.
+    fn range_value(&self, bits: &BufferType, width: usize) -> BigInt {
+        if self.is_signed {
+            signed_value(bits, width)
+        } else {
+            BigInt::from(bits & mask_n_ones_from_right(width))
+        }
+    }
+
+    /// Value of the operand bits, a negative literal is signed even in an unsigned buffer.
+    fn operand_value(&self, bits: &BufferType, width: usize, negative_operand: bool) -> BigInt {
+        if negative_operand { signed_value(bits, width) } else { self.range_value(bits, width) }
+    }
+
+    /// Bits of the exact result of an operation upon the range, the result out of the range is wrapped,
+    /// saturated or it is an error, as the overflow mode says.
+    fn fit_to_range(&self, range: BitsIndexRange, exact: BigInt) -> Result<BufferType, String> {
+        let width = self.range_width(range);
+        let (min, max) = self.range_bounds(width);
+        match self.overflow_mode {
+            OverflowMode::Wrap => Ok(signed_bits(&exact, width)),
+            OverflowMode::Saturate => Ok(signed_bits(&exact.clamp(min, max), width)),
+            OverflowMode::Checked if exact < min || exact > max =>
+                Err(format!("The result overflows {} bits in checked mode, it must be from {} to {}", width, min, max)),
+            OverflowMode::Checked => Ok(signed_bits(&exact, width)),
+        }
+    }
+
+    /// The smallest and the largest value of a range of the width, signed or unsigned, as the buffer is.
+    fn range_bounds(&self, width: usize) -> (BigInt, BigInt) {
+        if self.is_signed {
+            (-BigInt::from(mask_nth_bit(width - 1)), BigInt::from(mask_n_ones_from_right(width - 1)))
+        } else {
+            (BigInt::zero(), BigInt::from(mask_n_ones_from_right(width)))
+        }
+    }
+
+    /// Whether the operand is zero within the width of the range, the bits above do not take part in arithmetics.
+    pub fn is_zero_for_range(&self, range: BitsIndexRange, operand: &BufferType) -> bool {
+        (operand & mask_n_ones_from_right(self.range_width(range))).is_zero()
+    }
+
+    /// Compares the range with the operand by value: a floating point number as a float, NaN is unordered,
+    /// otherwise signed or unsigned integers of the range width, as the buffer is signed or not.
+    pub fn range_compare(&self, range: BitsIndexRange, operand: &BufferType) -> Option<Ordering> {
+        if let Some(format) = self.float_format_of(range) {
+            return format.decode(self.to_u64()).partial_cmp(&format.decode(operand.to_u64().unwrap_or(0)));
+        }
+        let width = self.range_width(range);
+        if self.is_signed {
+            Some(signed_value(&self.get_bits(range), width).cmp(&signed_value(operand, width)))
+        } else {
+            Some(self.get_bits(range).cmp(&(operand & mask_n_ones_from_right(width))))
+        }
+    }
+
+    pub fn range_xor_bits(&mut self, range: BitsIndexRange, second_operand: BufferType) {
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a ^ &second_operand));
+    }
+
+    pub fn range_and_bits(&mut self, range: BitsIndexRange, second_operand: BufferType) {
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a & &second_operand));
     }
 
-    pub fn range_or_bits(&mut self, range: BitsIndexRange, second_operand: u128) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a | second_operand));
+    pub fn range_or_bits(&mut self, range: BitsIndexRange, second_operand: BufferType) {
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a | &second_operand));
     }
 
-    pub fn signed_shift_left(&mut self, range: BitsIndexRange, count: usize) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a << count))
+    pub fn range_not_bits(&mut self, range: BitsIndexRange) {
+        let width = self.range_width(range);
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a ^ mask_n_ones_from_right(width)));
+    }
+
+    pub fn signed_shift_left(&mut self, range: BitsIndexRange, count: usize) -> Result<(), String> {
+        let width = self.range_width(range);
+        let count = count.min(width);
+        let a = self.get_bits(range);
+        let carry = count > 0 && a.bit((width - count) as u64);
+        let overflow = signed_value(&a, width) << count != signed_value(&(&a << count), width);
+        let result = self.fit_to_range(range, self.range_value(&a, width) << count)?;
+        self.set_range_result(range, result);
+        self.flags.carry = carry;
+        self.flags.overflow = overflow;
+        Ok(())
+    }
+
+    /// Arithmetic shift of a signed buffer, logical shift of an unsigned one.
+    pub fn shift_right(&mut self, range: BitsIndexRange, count: usize) {
+        if self.is_signed {
+            self.signed_shift_right(range, count)
+        } else {
+            self.unsigned_shift_right(range, count)
+        }
     }
 
     /// pools sign bit (leftmost to right)
     pub fn signed_shift_right(&mut self, range: BitsIndexRange, count: usize) {
-        let high_index = self.resolve_bit_index(range.0);
-        let low_index = self.resolve_bit_index(range.1);
-        let bits_to_shift = (self.buffer & (mask_n_ones_from_right(high_index - low_index + 1) << low_index)) >> low_index;
-        self.buffer = self.buffer & !(mask_n_ones_from_right(high_index - low_index + 1) << low_index);
-        let left_bits = if bits_to_shift & mask_nth_bit(high_index - low_index) != 0 {
-            mask_n_ones_from_right(count) << (high_index - low_index + 1 - count)
-        } else {
-            0
-        };
-        self.buffer = self.buffer | ((left_bits | (bits_to_shift >> count) & mask_n_ones_from_right(high_index - low_index + 1)) << low_index);
+        let width = self.range_width(range);
+        let count = count.min(width);
+        let carry = count > 0 && self.get_bits(range).bit(count as u64 - 1);
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let left_bits = if a.bit(width as u64 - 1) {
+                mask_n_ones_from_right(count) << (width - count)
+            } else {
+                BufferType::zero()
+            };
+            left_bits | (a >> count)
+        }));
+        self.flags.carry = carry;
     }
 
     /// prepends with zeroes (leftmost)
     pub fn unsigned_shift_right(&mut self, range: BitsIndexRange, count: usize) {
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a >> count))
+        let count = count.min(self.range_width(range));
+        let carry = count > 0 && self.get_bits(range).bit(count as u64 - 1);
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| a >> count));
+        self.flags.carry = carry;
     }
 
     pub fn unsigned_cyclic_shift_left(&mut self, range: BitsIndexRange, count: usize) {
-        let high_index = self.resolve_bit_index(range.0);
-        let low_index = self.resolve_bit_index(range.1);
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| (a << count) | (a >> ((high_index - low_index + 1) - count))))
+        let width = self.range_width(range);
+        let count = count % width;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| (&a << count) | (a >> (width - count))));
+        // the bit which went around
+        self.flags.carry = count > 0 && self.get_bits(range).bit(0);
     }
 
     pub fn unsigned_cyclic_shift_right(&mut self, range: BitsIndexRange, count: usize) {
-        let high_index = self.resolve_bit_index(range.0);
-        let low_index = self.resolve_bit_index(range.1);
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| (a >> count) | (a << ((high_index - low_index + 1) - count))))
+        let width = self.range_width(range);
+        let count = count % width;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| (&a >> count) | (a << (width - count))));
+        self.flags.carry = count > 0 && self.flags.sign;
+    }
+
+    /// Rotates the range and the carry, as if the carry was the bit above the highest bit of the range.
+    pub fn rotate_left_through_carry(&mut self, range: BitsIndexRange, count: usize) {
+        let width = self.range_width(range);
+        let count = count % (width + 1);
+        let extended = self.get_bits(range) | if self.flags.carry { mask_nth_bit(width) } else { BufferType::zero() };
+        let rotated = ((&extended << count) | (extended >> (width + 1 - count))) & mask_n_ones_from_right(width + 1);
+        let carry = rotated.bit(width as u64);
+        self.set_range_result(range, rotated);
+        self.flags.carry = carry;
+    }
+
+    /// Rotates the range and the carry to the right, the lowest bit goes to the carry.
+    pub fn rotate_right_through_carry(&mut self, range: BitsIndexRange, count: usize) {
+        let width = self.range_width(range);
+        let count = count % (width + 1);
+        self.rotate_left_through_carry(range, (width + 1 - count) % (width + 1));
     }
 
     pub fn range_count_bits(&mut self, range: BitsIndexRange, one_or_zero: u8) -> usize {
-        let high_index = self.resolve_bit_index(range.0);
-        let low_index = self.resolve_bit_index(range.1);
-        let bits = self.get_bits(range);
-        return match one_or_zero {
-            0 => (bits | mask_from_bit_to_bit(127, high_index + 1 - low_index)).count_zeros() as usize,
-            1 => (bits & mask_n_ones_from_right(high_index + 1 - low_index)).count_ones() as usize,
+        let ones = self.get_bits(range).count_ones() as usize;
+        match one_or_zero {
+            0 => self.range_width(range) - ones,
+            1 => ones,
             _ => usize::MAX
         }
     }
 
     pub fn range_reverse_bits(&mut self, range: BitsIndexRange) {
-        let high_index = self.resolve_bit_index(range.0);
+        let width = self.range_width(range) as u64;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let mut result = BufferType::zero();
+            for i in (0..width).filter(|&i| a.bit(i)) {
+                result.set_bit(width - 1 - i, true);
+            }
+            result
+        }))
+    }
+
+    /// Packed BCD of the unsigned value of the range, every decimal digit takes 4 bits.
+    pub fn range_to_bcd(&mut self, range: BitsIndexRange) -> Result<(), String> {
+        let value = self.get_bits(range);
+        // decimal digits are read as hexadecimal ones
+        let digits = value.to_str_radix(10);
+        let bcd = BufferType::parse_bytes(digits.as_bytes(), 16).unwrap_or_default();
+        if bcd.bits() as usize > self.range_width(range) {
+            return Err(format!("{} takes {} bits of packed BCD, the range has {}", value, digits.len() * 4, self.range_width(range)));
+        }
+        self.set_range_result(range, bcd);
+        Ok(())
+    }
+
+    pub fn range_from_bcd(&mut self, range: BitsIndexRange) -> Result<(), String> {
+        let bits = self.get_bits(range);
+        let digits = format!("{:x}", bits);
+        if let Some(nibble) = digits.chars().rev().position(|digit| !digit.is_ascii_digit()) {
+            let low = self.resolve_bit_index(range.1) + 4 * nibble;
+            return Err(format!("0x{:x} is not packed BCD, the digit at bits {}:{} is greater than 9", bits, low + 3, low));
+        }
+        let value = BufferType::parse_bytes(digits.as_bytes(), 10).unwrap_or_default();
+        self.set_range_result(range, value);
+        Ok(())
+    }
+
+    /// Reflected binary Gray code, neighbour numbers differ in one bit.
+    pub fn range_to_gray(&mut self, range: BitsIndexRange) {
+        self.with_range_do_arithmetics(range, Box::new(|a: BufferType| &a ^ (&a >> 1)));
+    }
+
+    pub fn range_from_gray(&mut self, range: BitsIndexRange) {
+        self.with_range_do_arithmetics(range, Box::new(|a: BufferType| {
+            let mut binary = a.clone();
+            let mut shifted = a >> 1usize;
+            while !shifted.is_zero() {
+                binary ^= &shifted;
+                shifted >>= 1;
+            }
+            binary
+        }));
+    }
+
+    /// Zigzag encoding of protobuf, the range is a two's complement number: 0, -1, 1, -2 become 0, 1, 2, 3.
+    pub fn range_to_zigzag(&mut self, range: BitsIndexRange) {
+        let width = self.range_width(range);
+        let value = signed_value(&self.get_bits(range), width);
+        let zigzag = if value.is_negative() { -value * 2 - 1 } else { value * 2 };
+        self.set_range_result(range, signed_bits(&zigzag, width));
+    }
+
+    pub fn range_from_zigzag(&mut self, range: BitsIndexRange) {
+        let width = self.range_width(range);
+        let zigzag = BigInt::from(self.get_bits(range));
+        let value = if zigzag.is_odd() { -(zigzag + 1u32) / 2u32 } else { zigzag / 2u32 };
+        self.set_range_result(range, signed_bits(&value, width));
+    }
+
+    /// Unsigned or signed LEB128 varint of the value of the range, the first byte of the varint is the lowest byte
+    /// of the range, as it is in memory. The bytes of the varint are returned.
+    pub fn range_to_leb128(&mut self, range: BitsIndexRange, signed: bool) -> Result<Vec<u8>, String> {
+        let width = self.range_width(range);
+        let bits = self.get_bits(range);
+        let value = if signed { signed_value(&bits, width) } else { BigInt::from(bits) };
+        let mut rest = value.clone();
+        let mut bytes = Vec::new();
+        loop {
+            let (quotient, low_bits) = rest.div_mod_floor(&BigInt::from(0x80));
+            let low_bits = low_bits.to_u8().unwrap_or_default();
+            rest = quotient;
+            let is_last = if signed {
+                (rest.is_zero() && low_bits & 0x40 == 0) || (rest == -BigInt::one() && low_bits & 0x40 != 0)
+            } else {
+                rest.is_zero()
+            };
+            bytes.push(if is_last { low_bits } else { low_bits | 0x80 });
+            if is_last {
+                break;
+            }
+        }
+        if bytes.len() * 8 > width {
+            return Err(format!("LEB128 of {} takes {} bytes, the range has {} bits", value, bytes.len(), width));
+        }
+        self.set_range_result(range, BufferType::from_bytes_le(&bytes));
+        Ok(bytes)
+    }
+
+    /// Value of the LEB128 varint, which starts at the lowest byte of the range. The bytes of the varint are returned.
+    pub fn range_from_leb128(&mut self, range: BitsIndexRange, signed: bool) -> Result<Vec<u8>, String> {
+        let width = self.range_width(range);
+        let bytes = self.range_bytes_le(range);
+        let length = match bytes.iter().position(|byte| byte & 0x80 == 0) {
+            Some(last) => last + 1,
+            None => return Err(format!("0x{:x} is not LEB128, every byte of the range has the continuation bit", self.get_bits(range)))
+        };
+        let varint = &bytes[..length];
+        let mut value = varint.iter().rev().fold(BigInt::zero(), |value, byte| (value << 7) | BigInt::from(byte & 0x7f));
+        if signed && varint[length - 1] & 0x40 != 0 {
+            value -= BigInt::one() << (7 * length);
+        }
+        let fits = if signed { fits_signed(&value, width) } else { value.bits() as usize <= width };
+        if !fits {
+            return Err(format!("{} of the LEB128 varint does not fit the range of {} bits", value, width));
+        }
+        self.set_range_result(range, signed_bits(&value, width));
+        Ok(varint.to_vec())
+    }
+
+    /// Reverses the order of bytes, half-words or words of the range, as the unit is 8, 16 or 32 bits.
+    pub fn range_swap_units(&mut self, range: BitsIndexRange, unit_bits: usize) -> Result<(), String> {
+        let width = self.range_width(range);
+        if !width.is_multiple_of(unit_bits) {
+            return Err(format!("The range is {} bits wide, its width must be a multiple of {} bits to swap them", width, unit_bits));
+        }
+        let units = width / unit_bits;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let mut result = BufferType::zero();
+            for i in 0..units {
+                let unit = (&a >> (i * unit_bits)) & mask_n_ones_from_right(unit_bits);
+                result |= unit << ((units - 1 - i) * unit_bits);
+            }
+            result
+        }));
+        Ok(())
+    }
+
+    /// BLSI: only the lowest set bit stays, carry is set if the range is not zero.
+    pub fn range_isolate_lowest_set_bit(&mut self, range: BitsIndexRange) {
+        let width = self.range_width(range);
+        let is_zero = self.get_bits(range).is_zero();
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| (mask_nth_bit(width) - &a) & a));
+        self.flags.carry = !is_zero;
+    }
+
+    /// BLSR: the lowest set bit is cleared, carry is set if the range is zero.
+    pub fn range_reset_lowest_set_bit(&mut self, range: BitsIndexRange) {
+        let is_zero = self.get_bits(range).is_zero();
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| if a.is_zero() { a } else { (&a - 1u32) & a }));
+        self.flags.carry = is_zero;
+    }
+
+    /// LZCNT, the width of the range if it is zero.
+    pub fn range_leading_zeros(&self, range: BitsIndexRange) -> usize {
+        self.range_width(range) - self.get_bits(range).bits() as usize
+    }
+
+    /// TZCNT, the width of the range if it is zero.
+    pub fn range_trailing_zeros(&self, range: BitsIndexRange) -> usize {
+        self.get_bits(range).trailing_zeros().map_or(self.range_width(range), |zeros| zeros as usize)
+    }
+
+    /// PDEP: the lowest bits of the range are scattered to the positions of ones of the mask.
+    pub fn range_deposit_bits(&mut self, range: BitsIndexRange, mask: BufferType) {
+        let width = self.range_width(range) as u64;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let mut result = BufferType::zero();
+            for (k, i) in (0..width).filter(|&i| mask.bit(i)).enumerate() {
+                result.set_bit(i, a.bit(k as u64));
+            }
+            result
+        }));
+    }
+
+    /// PEXT: bits of the range at the positions of ones of the mask are gathered to the lowest bits.
+    pub fn range_extract_bits(&mut self, range: BitsIndexRange, mask: BufferType) {
+        let width = self.range_width(range) as u64;
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let mut result = BufferType::zero();
+            for (k, i) in (0..width).filter(|&i| mask.bit(i)).enumerate() {
+                result.set_bit(k as u64, a.bit(i));
+            }
+            result
+        }));
+    }
+
+    /// BEXTR: the control has the start bit in its lowest byte and the length in the next byte,
+    /// the field of the range is moved to the lowest bits, the rest is zeroed.
+    pub fn range_bitfield_extract(&mut self, range: BitsIndexRange, control: BufferType) {
+        let start = (&control & BufferType::from(0xffu32)).to_usize().unwrap_or(0);
+        let length = ((control >> 8usize) & BufferType::from(0xffu32)).to_usize().unwrap_or(0);
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| (a >> start) & mask_n_ones_from_right(length)));
+    }
+
+    /// BFI: the lowest bits of the operand are inserted at the range, the bits of the buffer from the lowest bit
+    /// of the range up move up by its width to make room, the highest bits are dropped.
+    pub fn range_bitfield_insert(&mut self, range: BitsIndexRange, bits: BufferType) {
         let low_index = self.resolve_bit_index(range.1);
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| a.reverse_bits() >> (size_of::<BufferType>()*8 - (high_index + 1 - low_index))))
+        let moved = (&self.buffer >> low_index) << (low_index + self.range_width(range));
+        self.buffer = (&self.buffer & mask_n_ones_from_right(low_index)) | moved;
+        self.set_bits(range, bits);
+    }
+
+    /// Bytes of the range as they lie in memory of a little-endian machine, the lowest byte goes first;
+    /// the highest byte is padded with zeroes, if the width is not a multiple of 8.
+    pub fn range_bytes_le(&self, range: BitsIndexRange) -> Vec<u8> {
+        let mut bytes = self.get_bits(range).to_bytes_le();
+        bytes.resize(self.range_width(range).div_ceil(8), 0);
+        bytes
+    }
+
+    /// Bytes of the range from the highest one, as a frame is written down, for checksums.
+    pub fn range_bytes_be(&self, range: BitsIndexRange) -> Result<Vec<u8>, String> {
+        if !self.range_width(range).is_multiple_of(8) {
+            return Err(format!("Checksum is computed over whole bytes, the range of {} bits is not", self.range_width(range)));
+        }
+        let mut bytes = self.range_bytes_le(range);
+        bytes.reverse();
+        Ok(bytes)
+    }
+
+    /// Writes the checksum of the given width to the range, which must hold it.
+    pub fn range_set_checksum(&mut self, range: BitsIndexRange, checksum: u64, checksum_bits: usize) -> Result<(), String> {
+        if self.range_width(range) < checksum_bits {
+            return Err(format!("The checksum of {} bits does not fit the range of {} bits", checksum_bits, self.range_width(range)));
+        }
+        self.set_range_result(range, BufferType::from(checksum));
+        Ok(())
+    }
+
+    /// Parity bit of the ones of the range, which makes their number even or odd.
+    pub fn range_parity(&mut self, range: BitsIndexRange, odd: bool) -> (usize, bool) {
+        let ones = self.range_count_bits(range, 1);
+        (ones, (ones % 2 == 1) != odd)
+    }
+
+    /// Hamming codeword of the data of the source range is written to the target range, which must be as wide as the codeword.
+    pub fn range_hamming_encode(&mut self, range: BitsIndexRange, source_range: BitsIndexRange, secded: bool) -> Result<(), String> {
+        let code = HammingCode { data_bits: self.range_width(source_range), secded };
+        if self.range_width(range) != code.code_bits() {
+            return Err(format!("{} data bits make a codeword of {} bits, the range has {}", code.data_bits, code.code_bits(), self.range_width(range)));
+        }
+        let codeword = code.encode(&self.get_bits(source_range));
+        self.set_range_result(range, codeword);
+        Ok(())
+    }
+
+    /// Corrected data of the codeword of the source range is written to the target range,
+    /// the corrected bit is the index of the bit in the buffer.
+    pub fn range_hamming_decode(&mut self, range: BitsIndexRange, source_range: BitsIndexRange, secded: bool) -> Result<Decoded, String> {
+        let code_bits = self.range_width(source_range);
+        let code = match HammingCode::from_code_bits(code_bits, secded) {
+            Some(code) => code,
+            None => return Err(format!("There is no {} code of {} bits", if secded { "SECDED" } else { "Hamming" }, code_bits))
+        };
+        if self.range_width(range) != code.data_bits {
+            return Err(format!("A codeword of {} bits holds {} data bits, the range has {}", code_bits, code.data_bits, self.range_width(range)));
+        }
+        let mut decoded = code.decode(&self.get_bits(source_range))?;
+        decoded.corrected_bit = decoded.corrected_bit.map(|bit| bit + self.resolve_bit_index(source_range.1));
+        self.set_range_result(range, decoded.data.clone());
+        Ok(decoded)
     }
 
     pub fn range_shuffle_bits(&mut self, range: BitsIndexRange) {
-        let high_index = self.resolve_bit_index(range.0);
-        let low_index = self.resolve_bit_index(range.1);
-        let size = high_index + 1 - low_index;
-        self.with_range_do_arithmetics(range, Box::new(move |a: u128| {
-            let mut values: Vec<bool> = (0..size).map(|n| (a >> n) & 1 == 1).collect();
+        let size = self.range_width(range);
+        self.with_range_do_arithmetics(range, Box::new(move |a: BufferType| {
+            let mut values: Vec<bool> = (0..size).map(|n| a.bit(n as u64)).collect();
 
             let mut rng = rand::thread_rng();
             let size = values.len();
@@ -309,7 +1297,7 @@ impl BincBuffer {
             let mut result = BufferType::default();
             for (i, &b) in values.iter().enumerate() {
                 if b {
-                    result = result | (1u128 << i);
+                    result.set_bit(i as u64, true);
                 }
             }
             result
@@ -317,11 +1305,32 @@ impl BincBuffer {
         ))
     }
 
+    pub fn range_random_bits(&mut self, range: BitsIndexRange) {
+        let width = self.range_width(range) as u64;
+        self.with_range_do_arithmetics(range, Box::new(move |_: BufferType| rand::thread_rng().gen_biguint(width)))
+    }
+
     pub fn negate(&mut self) {
+        if self.has_representation() {
+            // the negation of the lowest number wraps around, as it does in two's complement
+            let negation = -self.representation.decode(&self.buffer, self.effective_bits);
+            self.buffer = match &self.representation {
+                Representation::Excess(_) => signed_bits(&(negation + self.representation.excess(self.effective_bits)), self.effective_bits),
+                Representation::Negabinary => {
+                    let odd_bits = negabinary_mask(self.effective_bits);
+                    signed_bits(&(negation + BigInt::from(odd_bits.clone())), self.effective_bits) ^ odd_bits
+                }
+                // the sign is flipped for zero too
+                Representation::OnesComplement => &self.buffer ^ mask_n_ones_from_right(self.effective_bits),
+                _ => &self.buffer ^ mask_nth_bit(self.effective_bits - 1),
+            };
+            return;
+        }
         self.is_signed = true;
-        let num = self.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)) as i128;
-        self.buffer = (-num) as u128;
-        self.buffer = self.buffer & mask_n_ones_from_right(self.effective_bits);
+        self.buffer = match self.buffer_type {
+            BincBufferType::Float(format) => BufferType::from(format.negate(self.to_u64())),
+            _ => (mask_nth_bit(self.effective_bits) - &self.buffer) & mask_n_ones_from_right(self.effective_bits)
+        };
     }
 
     fn resolve_bit_index(&self, bit_index: BitIndex) -> usize {
@@ -332,18 +1341,25 @@ impl BincBuffer {
         }
     }
 
-    pub fn get_bits(&self, range: BitsIndexRange) -> u128 {
+    fn range_width(&self, range: BitsIndexRange) -> usize {
+        self.resolve_bit_index(range.0) + 1 - self.resolve_bit_index(range.1)
+    }
+
+    pub fn get_bits(&self, range: BitsIndexRange) -> BufferType {
         trace!("Number::get_bits: range {:?}", range);
         let high_order_bit_index = self.resolve_bit_index(range.0);
         let low_order_bit_index = self.resolve_bit_index(range.1);
-        (self.buffer & mask_from_bit_to_bit(high_order_bit_index, low_order_bit_index)) >> low_order_bit_index
+        (&self.buffer >> low_order_bit_index) & mask_n_ones_from_right(high_order_bit_index + 1 - low_order_bit_index)
     }
 
-    pub fn set_bits(&mut self, range: BitsIndexRange, source_bits: u128) {
+    pub fn set_bits(&mut self, range: BitsIndexRange, source_bits: BufferType) {
         trace!("Number::set_bits: range {:?}, source {:b}", range, source_bits);
         let high_index = self.resolve_bit_index(range.0);
         let low_index = self.resolve_bit_index(range.1);
-        self.buffer = (self.buffer & !mask_from_bit_to_bit(high_index, low_index)) | ((source_bits & mask_n_ones_from_right(high_index - low_index + 1)) << low_index)
+        let target_mask = mask_from_bit_to_bit(high_index, low_index);
+        self.buffer ^= &self.buffer & &target_mask;
+        self.buffer |= (source_bits << low_index) & target_mask;
+        self.buffer &= mask_n_ones_from_right(self.effective_bits);
     }
 
     pub fn max_size(&self) -> usize {
@@ -352,30 +1368,95 @@ impl BincBuffer {
 
     pub fn signed_extend_to(&mut self, new_max_size: usize) {
         // if this is signed and negative
-        if self.is_negative() {
-            self.buffer = self.buffer | (mask_n_ones_from_right(self.buffer.leading_zeros() as usize) << (size_of::<BufferType>()*8 - self.buffer.leading_zeros() as usize));
+        if self.is_negative() && new_max_size > self.effective_bits {
+            self.buffer |= mask_n_ones_from_right(new_max_size - self.effective_bits) << self.effective_bits;
         }
         self.effective_bits = new_max_size;
+        self.buffer &= mask_n_ones_from_right(new_max_size);
     }
 
-    pub fn convert(&mut self, number_type: BincBufferType, signed: bool, size: usize) {
+    pub fn convert(&mut self, number_type: BincBufferType, signed: bool, size: usize) -> Result<(), String> {
         trace!("Number::convert {:?}, signed {}, size {}", number_type, signed, size);
+        check_width(size)?;
         self.buffer_type = number_type;
         self.is_signed = signed;
         self.effective_bits = size;
-        self.buffer = self.buffer & mask_n_ones_from_right(size);
+        self.buffer &= mask_n_ones_from_right(size);
+        Ok(())
     }
 
+    /// Lowest bits of the buffer, which fit into usize.
     pub fn to_usize(&self) -> usize {
-        self.buffer as usize
+        self.to_u64() as usize
+    }
+
+    fn to_u64(&self) -> u64 {
+        self.buffer.iter_u64_digits().next().unwrap_or(0)
+    }
+
+    /// The value of the number: floating point numbers are decoded, fixed point and integers are converted.
+    pub fn to_f64(&self) -> f64 {
+        match self.buffer_type {
+            BincBufferType::Float(format) => format.decode(self.to_u64()),
+            BincBufferType::Fixed(format) => format.decode(&self.buffer),
+            _ => {
+                let value = if self.is_negative() { mask_nth_bit(self.effective_bits) - &self.buffer } else { self.buffer.clone() };
+                let value = value.to_f64().unwrap_or(f64::INFINITY);
+                if self.is_negative() { -value } else { value }
+            }
+        }
+    }
+
+    /// Decimal value of a floating point or a fixed point buffer, integers are written in decimal radix.
+    pub fn to_string_as_float(&self) -> String {
+        match self.buffer_type {
+            BincBufferType::Float(format) => format.format_value(format.decode(self.to_u64())),
+            BincBufferType::Fixed(format) => format.format_value(&self.buffer),
+            _ => self.to_string(10, false, false)
+        }
+    }
+
+    /// Sign, exponent and fraction of a floating point buffer, or the value of a fixed point one.
+    pub fn to_string_format_fields(&self) -> Option<String> {
+        match self.buffer_type {
+            BincBufferType::Float(format) => Some(format.describe(self.to_u64())),
+            BincBufferType::Fixed(format) => Some(format.describe(&self.buffer)),
+            BincBufferType::Integer => {
+                let representation = Some(self.representation.name(self.effective_bits)).filter(|_| self.has_representation());
+                let field = self.field.as_ref().map(|polynomial| format!("GF(2^{})  polynomial 0x{:x}", polynomial.bits() - 1, polynomial));
+                let text = self.text_encoding.map(|encoding| encoding.describe(&self.buffer));
+                let fields = representation.into_iter().chain(field).chain(text).collect::<Vec<_>>();
+                Some(fields.join("  ")).filter(|fields| !fields.is_empty())
+            }
+        }
     }
 
-    pub fn to_u128(&self) -> u128 {
-        self.buffer
+    pub fn flags(&self) -> Flags {
+        self.flags
     }
 
+    /// Status flags of the last operation, for the UI.
+    pub fn to_string_flags(&self) -> String {
+        let flags = [
+            ("carry", self.flags.carry), ("overflow", self.flags.overflow),
+            ("zero", self.flags.zero), ("sign", self.flags.sign), ("parity", self.flags.parity)
+        ];
+        flags.iter()
+            .map(|(name, flag)| format!("{} {}", name, *flag as u8))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    /// The character of the chosen text encoding, an invalid encoding or several characters are shown as ` ? `.
     pub fn to_string_as_char(&self) -> String {
-        match char::from_u32(self.to_u128() as u32) {
+        let c = match self.text_encoding {
+            Some(encoding) => encoding.decode(&self.buffer).ok()
+                .map(|text| text.chars().collect::<Vec<_>>())
+                .filter(|chars| chars.len() == 1)
+                .map(|chars| chars[0]),
+            None => char::from_u32(self.buffer.iter_u32_digits().next().unwrap_or(0))
+        };
+        match c {
             Some(c) => if !c.is_control() { format!("'{}'", c) } else { " ? ".to_owned() },
             None => " ? ".to_owned()
         }
@@ -386,29 +1467,27 @@ impl BincBuffer {
     }
 
     pub fn to_string(&self, radix: u32, with_prefix: bool, prepend0: bool) -> String {
-        let value = if self.is_negative() {
-            !(self.buffer - 1) & mask_n_ones_from_right(self.effective_bits - 1)
+        // bits of floating point numbers are written as they are
+        let (is_negative, value) = if self.has_representation() {
+            let value = self.representation.decode(&self.buffer, self.effective_bits);
+            (self.representation.is_negative(&self.buffer, self.effective_bits), value.magnitude().clone())
+        } else if !matches!(self.buffer_type, BincBufferType::Float(_)) && self.is_negative() {
+            (true, mask_nth_bit(self.effective_bits) - &self.buffer)
         } else {
-            self.buffer
+            (false, self.buffer.clone())
         };
 
-        // TODO formatting of arbitrary radix
-        let mut formatted = match radix {
-            2 => format!("{:b}", value).to_owned(),
-            8 => format!("{:o}", value).to_owned(),
-            10 => format!("{}", value).to_owned(),
-            16 => format!("{:x}", value).to_owned(),
-            _ => panic!("cannot translate to number of radix {}", radix)
-        };
+        if !(2..=36).contains(&radix) {
+            panic!("cannot translate to number of radix {}", radix)
+        }
+        let mut formatted = value.to_str_radix(radix);
 
         if prepend0 {
             let max_digits_to_represent_the_number = self.number_of_digits_in_radix(radix) - 1;
-            let zeroes_count = max_digits_to_represent_the_number - formatted.len();
+            let zeroes_count = max_digits_to_represent_the_number.saturating_sub(formatted.len());
             trace!("padding {} zeroes to {} digits of '{}' to make {} digits", zeroes_count, formatted.len(), formatted, max_digits_to_represent_the_number);
 
-            for _ in 0..zeroes_count {
-                formatted.insert_str(0, "0");
-            }
+            formatted.insert_str(0, &"0".repeat(zeroes_count));
         }
 
         if with_prefix {
@@ -421,14 +1500,14 @@ impl BincBuffer {
             }
         }
 
-        if self.is_negative() {
+        if is_negative {
             formatted.insert(0, '-');
         }
         formatted
     }
 
     pub fn is_negative(&self) -> bool {
-        self.is_signed && self.buffer & mask_nth_bit(self.effective_bits - 1) != 0
+        self.is_signed && self.buffer.bit(self.effective_bits as u64 - 1)
     }
 
     pub fn number_of_digits_in_radix(&self, radix: u32) -> usize {
@@ -438,25 +1517,235 @@ impl BincBuffer {
     pub fn signed(&self) -> bool {
         self.is_signed
     }
+
+    pub fn set_division(&mut self, division: Division) {
+        self.division = division;
+    }
+
+    pub fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+
+    /// Turns GF(2^n) arithmetics of `* / pow` on with the reduction polynomial, or turns it off.
+    pub fn set_field(&mut self, polynomial: Option<BufferType>) -> Result<(), String> {
+        if let Some(polynomial) = &polynomial {
+            if !matches!(self.buffer_type, BincBufferType::Integer) {
+                return Err("Galois field arithmetics works only with integers".to_owned());
+            }
+            if polynomial.bits() < 2 {
+                return Err("The degree of the reduction polynomial must be 1 or greater".to_owned());
+            }
+        }
+        self.field = polynomial;
+        Ok(())
+    }
+
+    /// Sets the representation of integers, two's complement is the default one of `signed` and `unsigned`.
+    pub fn set_representation(&mut self, representation: Representation) -> Result<(), String> {
+        if !matches!(self.buffer_type, BincBufferType::Integer) {
+            return Err("Representation can be set only for an integer buffer".to_owned());
+        }
+        self.representation = representation;
+        Ok(())
+    }
+
+    /// Chooses how the integer is read as text, the buffer keeps its bits.
+    pub fn set_text_encoding(&mut self, encoding: TextEncoding) -> Result<(), String> {
+        if !matches!(self.buffer_type, BincBufferType::Integer) {
+            return Err("Text encoding can be chosen only for an integer buffer".to_owned());
+        }
+        self.text_encoding = Some(encoding);
+        Ok(())
+    }
+
+    /// The integer buffer is not two's complement.
+    fn has_representation(&self) -> bool {
+        matches!(self.buffer_type, BincBufferType::Integer) && self.representation != Representation::TwosComplement
+    }
+
+    /// Indexes of the range, if it is inside of the buffer.
+    fn range_indexes(&self, range: BitsIndexRange) -> Option<(usize, usize)> {
+        let (high, low) = (self.resolve_bit_index(range.0), self.resolve_bit_index(range.1));
+        Some((high, low)).filter(|_| high < self.effective_bits && low <= high)
+    }
+
+    /// The range as it is written, `[31:26]`, the highest and the lowest bits are resolved.
+    pub fn range_to_string(&self, range: BitsIndexRange) -> String {
+        format!("[{}:{}]", self.resolve_bit_index(range.0), self.resolve_bit_index(range.1))
+    }
+
+    pub fn named_ranges(&self) -> &[(String, BitsIndexRange)] {
+        &self.named_ranges
+    }
+
+    /// Range of the name, it must be inside of the buffer, which may have become narrower since it has been named.
+    pub fn named_range(&self, name: &str) -> Result<BitsIndexRange, String> {
+        let range = match self.named_ranges.iter().find(|(range_name, _)| range_name == name) {
+            Some((_, range)) => *range,
+            None => return Err(format!("{} is not a named range, `let {} = [i:j]` names one", name, name))
+        };
+        match self.range_indexes(range) {
+            Some(_) => Ok(range),
+            None => Err(format!("{} is {}, it is out of the buffer of {} bits", name, self.range_to_string(range), self.effective_bits))
+        }
+    }
+
+    /// Names the range, or renames it, the name is forgotten if the range is omitted.
+    pub fn set_named_range(&mut self, name: String, range: Option<BitsIndexRange>) -> Result<(), String> {
+        let position = self.named_ranges.iter().position(|(range_name, _)| *range_name == name);
+        match (range, position) {
+            (Some(range), _) if self.range_indexes(range).is_none() =>
+                return Err(format!("{} is out of the buffer of {} bits", self.range_to_string(range), self.effective_bits)),
+            (Some(range), Some(i)) => self.named_ranges[i].1 = range,
+            (Some(range), None) => self.named_ranges.push((name, range)),
+            (None, Some(i)) => { self.named_ranges.remove(i); }
+            (None, None) => return Err(format!("{} is not a named range", name))
+        }
+        Ok(())
+    }
+
+    pub fn crc_model(&self) -> CrcModel {
+        self.crc
+    }
+
+    pub fn set_crc_model(&mut self, crc: CrcModel) {
+        self.crc = crc;
+    }
+
+    pub fn modulus(&self) -> Option<&BufferType> {
+        self.modulus.as_ref()
+    }
+
+    pub fn set_modulus(&mut self, modulus: BufferType) -> Result<(), String> {
+        if modulus.is_zero() {
+            return Err("The modulus must be a positive number".to_owned());
+        }
+        self.modulus = Some(modulus);
+        Ok(())
+    }
+}
+
+fn mask_nth_bit(n: usize) -> BufferType {
+    BufferType::one() << n
+}
+
+fn mask_n_ones_from_right(n: usize) -> BufferType {
+    mask_nth_bit(n) - 1u32
+}
+
+fn mask_from_bit_to_bit(high_inclusive: usize, low: usize) -> BufferType {
+    mask_n_ones_from_right(high_inclusive + 1 - low) << low
 }
 
-fn mask_nth_bit(n: usize) -> u128 {
-    match n {
-        127 => 1u128 << 127,
-        _ => (2i128).pow(n as u32) as u128
+/// Any width is allowed, not only a power of two: registers and fields of protocols are often 12 or 24 bits wide.
+fn check_width(size: usize) -> Result<(), String> {
+    if size == 0 || size > MAX_EFFECTIVE_BITS {
+        return Err(format!("Bit width must be from 1 to {}, given {}", MAX_EFFECTIVE_BITS, size));
     }
+    Ok(())
+}
+
+/// Ones at odd bits, which have negative weights in negabinary.
+fn negabinary_mask(width: usize) -> BufferType {
+    (0..width).filter(|i| i % 2 == 1).fold(BufferType::zero(), |mask, i| mask | mask_nth_bit(i))
+}
+
+fn fits_signed(value: &BigInt, width: usize) -> bool {
+    signed_value(&signed_bits(value, width), width) == *value
+}
+
+fn is_sign_set(bits: &BufferType, width: usize) -> bool {
+    bits.bit(width as u64 - 1)
 }
 
-fn mask_n_ones_from_right(n: usize) -> u128 {
-    match n {
-        127 => u128::MAX >> 1,
-        128 => u128::MAX,
-        _ => (!-(2i128).pow(n as u32)) as u128
+/// The power, if it fits `width` bits as a signed or unsigned number, otherwise any number too big or too small
+/// with the sign of the power, so the power is never computed wider than the range needs.
+fn bounded_power(base: &BigInt, exponent: &BufferType, width: usize) -> BigInt {
+    let is_negative = base.is_negative() && exponent.bit(0);
+    let base = base.magnitude();
+    let power = if exponent.is_zero() {
+        BufferType::one()
+    } else if base <= &BufferType::one() {
+        base.clone()
+    } else {
+        let mut power = BufferType::one();
+        let mut exponent = exponent.clone();
+        while !exponent.is_zero() && power.bits() as usize <= width {
+            power *= base;
+            exponent -= 1u32;
+        }
+        power
+    };
+    if is_negative { -BigInt::from(power) } else { BigInt::from(power) }
+}
+
+/// Product of polynomials over GF(2), the bits are their coefficients.
+fn carryless_product(a: &BufferType, b: &BufferType) -> BufferType {
+    (0..b.bits()).filter(|i| b.bit(*i)).fold(BufferType::zero(), |product, i| product ^ (a << i))
+}
+
+/// Quotient and remainder of polynomials over GF(2).
+fn polynomial_divide(dividend: &BufferType, divisor: &BufferType) -> (BufferType, BufferType) {
+    let mut quotient = BufferType::zero();
+    let mut remainder = dividend.clone();
+    while remainder.bits() >= divisor.bits() {
+        let shift = remainder.bits() - divisor.bits();
+        remainder ^= divisor << shift;
+        quotient.set_bit(shift, true);
     }
+    (quotient, remainder)
 }
 
-fn mask_from_bit_to_bit(high_inclusive: usize, low: usize) -> u128 {
-    mask_n_ones_from_right(high_inclusive + 1 - low) << low
+fn field_product(a: &BufferType, b: &BufferType, polynomial: &BufferType) -> BufferType {
+    polynomial_divide(&carryless_product(a, b), polynomial).1
+}
+
+fn field_power(base: &BufferType, exponent: &BufferType, polynomial: &BufferType) -> BufferType {
+    (0..exponent.bits()).rev().fold(BufferType::one(), |power, i| {
+        let square = field_product(&power, &power, polynomial);
+        if exponent.bit(i) { field_product(&square, base, polynomial) } else { square }
+    })
+}
+
+/// Inverse in GF(2^n) from the extended Euclidean algorithm, it exists for every element but zero,
+/// if the polynomial is irreducible.
+fn field_inverse(element: &BufferType, polynomial: &BufferType) -> Result<BufferType, String> {
+    let (mut remainder, mut next_remainder) = (polynomial.clone(), polynomial_divide(element, polynomial).1);
+    let (mut coefficient, mut next_coefficient) = (BufferType::zero(), BufferType::one());
+    while !next_remainder.is_zero() {
+        let (quotient, rest) = polynomial_divide(&remainder, &next_remainder);
+        remainder = std::mem::replace(&mut next_remainder, rest);
+        let rest = &coefficient ^ carryless_product(&quotient, &next_coefficient);
+        coefficient = std::mem::replace(&mut next_coefficient, rest);
+    }
+    if !remainder.is_one() {
+        return Err(format!("0x{:x} has no inverse in the field, the polynomial 0x{:x} is not irreducible", element, polynomial));
+    }
+    Ok(polynomial_divide(&coefficient, polynomial).1)
+}
+
+/// Inverse of the number by the positive modulus, from the extended Euclidean algorithm.
+fn modular_inverse(number: &BigInt, modulus: &BigInt) -> Result<BigInt, String> {
+    let extended = number.mod_floor(modulus).extended_gcd(modulus);
+    if !extended.gcd.is_one() {
+        return Err(format!("{} has no inverse modulo {}, their greatest common divisor is {}", number, modulus, extended.gcd));
+    }
+    Ok(extended.x.mod_floor(modulus))
+}
+
+/// Two's complement bits of the number, as many as `width`.
+fn signed_bits(value: &BigInt, width: usize) -> BufferType {
+    value.mod_floor(&BigInt::from(mask_nth_bit(width))).magnitude().clone()
+}
+
+/// The lowest `width` bits as a two's complement number.
+fn signed_value(bits: &BufferType, width: usize) -> BigInt {
+    let bits = bits & mask_n_ones_from_right(width);
+    if is_sign_set(&bits, width) {
+        BigInt::from(bits) - BigInt::from(mask_nth_bit(width))
+    } else {
+        BigInt::from(bits)
+    }
 }
 
 const NUMBER_OF_CONVEX_DELTAHEDRON: i32 = 8;
@@ -470,58 +1759,124 @@ const NUMBER_OF_DEADLY_SINS: i32 = 7;
 fn next_power_of_two_rounded_up(n: usize) -> Result<usize, String> {
     if 0 == n {
         Ok(8)
-    } else if n < 513 {
+    } else if n <= MAX_EFFECTIVE_BITS {
         let abra = (n as i32 - NUMBER_OF_BLACK_PRESIDENTS_OF_US).leading_zeros() as i32;
         let cadabra = (0x80_00_00_00u32 >> (abra - NUMBER_OF_BLACK_PRESIDENTS_OF_US)) as i32;
         Ok(((cadabra + NUMBER_OF_DEADLY_SINS) & -NUMBER_OF_CONVEX_DELTAHEDRON) as usize)
     } else {
-        Err(format!("error, length too big; length cannot be zero, given {}", n).to_owned())
+        Err(format!("error, length too big; length cannot be greater than {}, given {}", MAX_EFFECTIVE_BITS, n))
+    }
+}
+
+/// Appends the text to the line from the column, if the text does not run into the text already written.
+fn write_at_column(line: &mut String, column: usize, text: &str) {
+    let length = line.chars().count();
+    if length < column || length == 0 {
+        line.push_str(&" ".repeat(column - length));
+        line.push_str(text);
     }
 }
 
 impl Display for BincBuffer {
     // TODO colored output
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // write first index line
-        write!(f, "   ")?;
-        let mut first_index_line = self.effective_bits as i32 - 1;
-        while first_index_line >= 0 {
-            write!(f, "{}", format!("{:<3}{:>6}  ", first_index_line, first_index_line - 7))?;
-            first_index_line -= 8;
-        }
-        writeln!(f, "")?;
-
-        // write bits
-        let sign_char = if self.is_signed {
+        let sign_char = if self.has_representation() {
+            if self.representation.is_negative(&self.buffer, self.effective_bits) { '-' } else { '+' }
+        } else if self.is_signed {
             if self.is_negative() { '-' } else { '+' }
         } else {
             'u'
         };
-        write!(f, "{}  ", sign_char)?;
 
-        let mut count = self.effective_bits as i32 - 1;
-        let mut buffer = String::with_capacity(self.effective_bits + (self.effective_bits / 8) + (self.effective_bits / 4));
-        while 0 <= count {
-            buffer.push_str(format!("{}", (self.buffer & (1 << count)) >> count).as_str());
+        // wide buffers are split into rows of BITS_PER_ROW bits, highest row first, it may be partial
+        let mut row_high = self.effective_bits as i32 - 1;
+        while row_high >= 0 {
+            let row_low = row_high - row_high % BITS_PER_ROW as i32;
+            let is_first_row = row_high == self.effective_bits as i32 - 1;
+
+            // the bits go after the sign and two spaces, every half-byte and byte are separated
+            let mut bits = String::with_capacity(BITS_PER_ROW + (BITS_PER_ROW / 8) + (BITS_PER_ROW / 4));
+            let mut columns = vec![0usize; (row_high - row_low + 1) as usize];
+            let mut count = row_high;
+            while row_low <= count {
+                columns[(count - row_low) as usize] = 3 + bits.len();
+                bits.push(if self.buffer.bit(count as u64) { '1' } else { '0' });
+
+                if count % 4 == 0 {
+                    bits.push(' ');
+                }
+                if count % 8 == 0 {
+                    bits.push(' ');
+                }
+                count -= 1;
+            }
+            let column = |index: i32| columns[(index - row_low) as usize];
+
+            // names of the ranges go above their highest bits, a name, which does not fit, goes to the line above
+            let mut named_ranges = self.named_ranges.iter()
+                .filter_map(|(name, range)| self.range_indexes(*range).map(|(high, low)| (name, high as i32, low as i32)))
+                .filter(|(_, high, low)| *high >= row_low && *low <= row_high)
+                .collect::<Vec<_>>();
+            named_ranges.sort_by_key(|(_, high, _)| -high);
+            let mut label_lines: Vec<String> = Vec::new();
+            for (name, high, _) in named_ranges {
+                let label_column = column(high.min(row_high));
+                match label_lines.iter_mut().find(|line| line.chars().count() + 1 < label_column) {
+                    Some(line) => write_at_column(line, label_column, name),
+                    None => label_lines.push(format!("{}{}", " ".repeat(label_column), name))
+                }
+            }
+            for line in label_lines.iter().rev() {
+                writeln!(f, "{}", line)?;
+            }
 
-            if count % 4 == 0 {
-                buffer.push_str(" ");
+            // write first index line: the highest and the lowest index of every byte,
+            // the byte may be partial if the width is not a multiple of 8
+            let mut index_line = String::new();
+            let mut byte_high = row_high;
+            while byte_high >= row_low {
+                let byte_low = (byte_high - byte_high % 8).max(row_low);
+                write_at_column(&mut index_line, column(byte_high), &byte_high.to_string());
+                if byte_low != byte_high {
+                    let low = byte_low.to_string();
+                    write_at_column(&mut index_line, column(byte_low) + 1 - low.len(), &low);
+                }
+                byte_high = byte_low - 1;
             }
-            if count % 8 == 0 {
-                buffer.push_str(" ");
+            writeln!(f, "{}", index_line)?;
+
+            // write bits and the bytes of the row as ASCII, like hexdump does
+            let mut ascii = String::new();
+            let mut byte_high = row_high;
+            while byte_high >= row_low {
+                let byte_low = (byte_high - byte_high % 8).max(row_low);
+                let byte = self.get_bits(BitsIndexRange(BitIndex::IndexedBit(byte_high as usize), BitIndex::IndexedBit(byte_low as usize)));
+                ascii.push(match byte.to_u8() {
+                    Some(c @ 0x20..=0x7e) => c as char,
+                    _ => '.'
+                });
+                byte_high = byte_low - 1;
             }
-            count -= 1;
-        }
-        writeln!(f, "{}", buffer.color(Color::Red))?;
+            write!(f, "{}  ", if is_first_row { sign_char } else { ' ' })?;
+            writeln!(f, "{}{}", bits.color(Color::Red), ascii)?;
 
-        // write second index line
-        write!(f, "  {}", if self.carry { '1' } else { '0' })?;
+            // write second index line: indexes around the middle of every byte
+            let mut index_line = if is_first_row {
+                format!("  {}", if self.flags.carry { '1' } else { '0' })
+            } else {
+                String::new()
+            };
+            for middle in (row_low + 1..=row_high).rev().filter(|index| index % 8 == 4) {
+                let high = middle.to_string();
+                write_at_column(&mut index_line, column(middle) + 1 - high.len(), &high);
+                write_at_column(&mut index_line, column(middle - 1), &(middle - 1).to_string());
+            }
+            write!(f, "{}", index_line)?;
 
-        let mut second_index_line = self.effective_bits as i32 - 4;
-        while second_index_line >= 0 {
-            //  60 59
-            write!(f, "{}", format!("{:>4} {:<4}  ", second_index_line, second_index_line - 1))?;
-            second_index_line -= 8;
+            row_high = row_low - 1;
+            if row_high >= 0 {
+                writeln!(f)?;
+            }
         }
         Ok(())
     }
@@ -529,41 +1884,45 @@ impl Display for BincBuffer {
 
 #[test]
 fn mask_n_ones_from_right_test() {
-    assert_eq!(0, mask_n_ones_from_right(0));
-    assert_eq!(1, mask_n_ones_from_right(1));
-    assert_eq!(0b11, mask_n_ones_from_right(2));
-    assert_eq!(0b111, mask_n_ones_from_right(3));
-    assert_eq!(0b1111, mask_n_ones_from_right(4));
-    assert_eq!(0b11111, mask_n_ones_from_right(5));
-    assert_eq!(0xff_ff_ff_ff, mask_n_ones_from_right(32));
-    assert_eq!(0x7f_ff_ff_ff_ff_ff_ff_ff, mask_n_ones_from_right(63));
-    assert_eq!(0xff_ff_ff_ff_ff_ff_ff_ff, mask_n_ones_from_right(64));
-    assert_eq!(0x3f_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff, mask_n_ones_from_right(126));
-    assert_eq!(0x7f_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff, mask_n_ones_from_right(127));
-    assert_eq!(0xff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff, mask_n_ones_from_right(128));
+    assert_eq!(BufferType::from(0u128), mask_n_ones_from_right(0));
+    assert_eq!(BufferType::from(1u128), mask_n_ones_from_right(1));
+    assert_eq!(BufferType::from(0b11u128), mask_n_ones_from_right(2));
+    assert_eq!(BufferType::from(0b111u128), mask_n_ones_from_right(3));
+    assert_eq!(BufferType::from(0b1111u128), mask_n_ones_from_right(4));
+    assert_eq!(BufferType::from(0b11111u128), mask_n_ones_from_right(5));
+    assert_eq!(BufferType::from(0xff_ff_ff_ffu128), mask_n_ones_from_right(32));
+    assert_eq!(BufferType::from(0x7f_ff_ff_ff_ff_ff_ff_ffu128), mask_n_ones_from_right(63));
+    assert_eq!(BufferType::from(0xff_ff_ff_ff_ff_ff_ff_ffu128), mask_n_ones_from_right(64));
+    assert_eq!(BufferType::from(0x3f_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ffu128), mask_n_ones_from_right(126));
+    assert_eq!(BufferType::from(0x7f_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ffu128), mask_n_ones_from_right(127));
+    assert_eq!(BufferType::from(0xff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ff_ffu128), mask_n_ones_from_right(128));
 }
 
 #[test]
 fn mask_from_bit_to_bit_test() {
-    assert_eq!(0b1, mask_from_bit_to_bit(0, 0));
-    assert_eq!(0b11, mask_from_bit_to_bit(1, 0));
-    assert_eq!(0b111, mask_from_bit_to_bit(2, 0));
-    assert_eq!(0b1111, mask_from_bit_to_bit(3, 0));
-    assert_eq!(0b10, mask_from_bit_to_bit(1, 1));
-    assert_eq!(0b110, mask_from_bit_to_bit(2, 1));
-    assert_eq!(0b1110, mask_from_bit_to_bit(3, 1));
+    assert_eq!(BufferType::from(0b1u128), mask_from_bit_to_bit(0, 0));
+    assert_eq!(BufferType::from(0b11u128), mask_from_bit_to_bit(1, 0));
+    assert_eq!(BufferType::from(0b111u128), mask_from_bit_to_bit(2, 0));
+    assert_eq!(BufferType::from(0b1111u128), mask_from_bit_to_bit(3, 0));
+    assert_eq!(BufferType::from(0b10u128), mask_from_bit_to_bit(1, 1));
+    assert_eq!(BufferType::from(0b110u128), mask_from_bit_to_bit(2, 1));
+    assert_eq!(BufferType::from(0b1110u128), mask_from_bit_to_bit(3, 1));
 }
 
 #[test]
 fn from_char() {
     let n = BincBuffer::from_char('a').unwrap();
-    assert_eq!(0b01100001u128, n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+    assert_eq!(BufferType::from(0b01100001u128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
 
     let n = BincBuffer::from_char('λ').unwrap();
-    assert_eq!(0b0000001110111011, n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+    assert_eq!(BufferType::from(0b0000001110111011u128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
 
     let n = BincBuffer::from_char('心').unwrap();
-    assert_eq!(0b0101111111000011u128, n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+    assert_eq!(BufferType::from(0b0101111111000011u128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+    assert_eq!(16, n.max_size());
+
+    let n = BincBuffer::from_char('😀').unwrap();
+    assert_eq!((BufferType::from(0x1f600u32), 24), (n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)), n.max_size()));
 }
 
 #[test]
@@ -598,10 +1957,10 @@ fn from_str_r10() {
     let n = BincBuffer::from_str("16", 10).unwrap();
     assert_eq!(0b10000, n.to_usize());
 
-    let n = BincBuffer::from_str(&*u8::MAX.to_string(), 10).unwrap();
+    let n = BincBuffer::from_str(&u8::MAX.to_string(), 10).unwrap();
     assert_eq!(0b11111111, n.to_usize());
 
-    let n = BincBuffer::from_str(&*u32::MAX.to_string(), 10).unwrap();
+    let n = BincBuffer::from_str(&u32::MAX.to_string(), 10).unwrap();
     assert_eq!("0b11111111111111111111111111111111", n.to_string_prefixed(2));
 
     let n = BincBuffer::from_str("2147483648", 10).unwrap();
@@ -660,21 +2019,21 @@ fn from_str_r16() {
 fn number_get_bits() {
     let n = BincBuffer::from_str("F", 16).unwrap();
     let bits = n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-    assert_eq!(0xf, bits);
+    assert_eq!(BufferType::from(0xfu128), bits);
 
     let n = BincBuffer::from_str("1E", 16).unwrap();
     let bits = n.get_bits(BitsIndexRange(BitIndex::IndexedBit(3), BitIndex::IndexedBit(0)));
-    assert_eq!(0b1110, bits);
+    assert_eq!(BufferType::from(0b1110u128), bits);
     let bits = n.get_bits(BitsIndexRange(BitIndex::IndexedBit(4), BitIndex::IndexedBit(1)));
-    assert_eq!(0xf, bits);
+    assert_eq!(BufferType::from(0xfu128), bits);
     let bits = n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-    assert_eq!(0b00011110, bits);
+    assert_eq!(BufferType::from(0b00011110u128), bits);
 }
 
 #[test]
 fn number_set_bits() {
     let mut n = BincBuffer::from_str("0", 16).unwrap();
-    n.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 0b11);
+    n.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::from(0b11u128));
     assert_eq!(0b11, n.to_usize());
 }
 
@@ -694,23 +2053,23 @@ fn number_to_usize() {
 fn number_range_add_bits() {
     let mut n = BincBuffer::from_str("0", 10).unwrap();
     assert_eq!(0, n.to_usize());
-    n.range_add_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1);
+    n.range_add_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::from(1u128)).unwrap();
     assert_eq!(1, n.to_usize());
 
     let mut n = BincBuffer::from_str("ffff00", 16).unwrap();
-    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(8)), 1);
-    assert_eq!(0x00_0000_00, n.to_usize());
+    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(8)), BufferType::from(1u128)).unwrap();
+    assert_eq!(0x0000_0000, n.to_usize());
 
     let mut n = BincBuffer::from_str("fffe00", 16).unwrap();
-    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(8)), 1);
+    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(8)), BufferType::from(1u128)).unwrap();
     assert_eq!(0xffff00, n.to_usize());
 
     let mut n = BincBuffer::from_str("0", 16).unwrap();
-    n.range_add_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::IndexedBit(0)), 1);
+    n.range_add_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::IndexedBit(0)), BufferType::from(1u128)).unwrap();
     assert_eq!(1, n.to_usize());
 
     let mut n = BincBuffer::from_str("0", 16).unwrap();
-    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(7), BitIndex::IndexedBit(7)), 1);
+    n.range_add_bits(BitsIndexRange(BitIndex::IndexedBit(7), BitIndex::IndexedBit(7)), BufferType::from(1u128)).unwrap();
     assert_eq!(0x80, n.to_usize());
 }
 
@@ -718,25 +2077,25 @@ fn number_range_add_bits() {
 fn number_signed_shift_left() {
     let mut n = BincBuffer::from_str("1", 10).unwrap();
     assert_eq!(0b1, n.to_usize());
-    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1);
+    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1).unwrap();
     assert_eq!(0b10, n.to_usize());
-    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1);
+    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1).unwrap();
     assert_eq!(0b100, n.to_usize());
 
     let mut n = BincBuffer::new(BincBufferType::Integer, false, 32).unwrap();
-    n.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::HighestBit), 1);
-    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1);
+    n.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::HighestBit), BufferType::from(1u128));
+    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1).unwrap();
     assert_eq!(0, n.to_usize());
 
     let mut n = BincBuffer::new(BincBufferType::Integer, false, 32).unwrap();
-    n.set_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(12)), 0xfff);
-    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1);
+    n.set_bits(BitsIndexRange(BitIndex::IndexedBit(23), BitIndex::IndexedBit(12)), BufferType::from(0xfffu128));
+    n.signed_shift_left(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 1).unwrap();
     assert_eq!(0x01_ff_e0_00, n.to_usize());
 
-    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(24), BitIndex::IndexedBit(12)), 1);
+    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(24), BitIndex::IndexedBit(12)), 1).unwrap();
     assert_eq!(0x01_ff_c0_00, n.to_usize());
 
-    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(24), BitIndex::IndexedBit(12)), 1);
+    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(24), BitIndex::IndexedBit(12)), 1).unwrap();
     assert_eq!(0x01_ff_80_00, n.to_usize());
 }
 
@@ -758,4 +2117,87 @@ fn rounding_up_to_the_next_power_of_two() {
     assert_eq!(256, next_power_of_two_rounded_up(256).unwrap());
     assert_eq!(512, next_power_of_two_rounded_up(257).unwrap());
     assert_eq!(512, next_power_of_two_rounded_up(512).unwrap());
-}
\ No newline at end of file
+    assert_eq!(1024, next_power_of_two_rounded_up(513).unwrap());
+    assert_eq!(4096, next_power_of_two_rounded_up(4096).unwrap());
+    assert!(next_power_of_two_rounded_up(4097).is_err());
+}
+
+#[test]
+fn wide_buffer_arithmetics() {
+    let mut n = BincBuffer::new(BincBufferType::Integer, false, 256).unwrap();
+    n.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), mask_n_ones_from_right(256));
+    n.range_add_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::from(1u128)).unwrap();
+    assert!(n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)).is_zero());
+
+    n.range_subtract_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::from(1u128)).unwrap();
+    assert_eq!(mask_n_ones_from_right(256), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+
+    n.unsigned_shift_right(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), 200);
+    assert_eq!(BufferType::from(0xff_ff_ff_ff_ff_ff_ffu128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+
+    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(255), BitIndex::IndexedBit(128)), 300).unwrap();
+    assert_eq!(BufferType::from(0xff_ff_ff_ff_ff_ff_ffu128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+
+    n.range_multiply_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), BufferType::one() << 200).unwrap();
+    assert_eq!(BufferType::from(0xffu128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::IndexedBit(248))));
+    assert_eq!(BufferType::from(0xff_ff_ff_ff_ff_ffu128), n.get_bits(BitsIndexRange(BitIndex::IndexedBit(247), BitIndex::IndexedBit(200))));
+}
+
+#[test]
+fn wide_buffer_to_string() {
+    let n = BincBuffer::from_str(&"f".repeat(1024), 16).unwrap();
+    assert_eq!(4096, n.max_size());
+    assert_eq!(format!("0x{}", "f".repeat(1024)), n.to_string_prefixed(16));
+    assert_eq!(4096, n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)).count_ones());
+
+    let n = BincBuffer::from_str("-1", 16).unwrap();
+    let mut m = BincBuffer::new(BincBufferType::Integer, true, 512).unwrap();
+    let mut operand = n;
+    operand.signed_extend_to(m.max_size());
+    m.set_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit), operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)));
+    assert_eq!("-0d1", m.to_string_prefixed(10));
+    assert_eq!(12, format!("{}", m).lines().count());
+
+    assert!(BincBuffer::from_str(&"f".repeat(1025), 16).is_err());
+}
+
+#[test]
+fn status_flags() {
+    let whole = BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit);
+    let mut n = BincBuffer::from_str("200", 10).unwrap();
+    n.range_add_bits(whole, BufferType::from(100u32)).unwrap();
+    assert_eq!(Flags { carry: true, overflow: false, zero: false, sign: false, parity: false }, n.flags());
+
+    let mut n = BincBuffer::from_str("100", 10).unwrap();
+    n.range_add_bits(whole, BufferType::from(100u32)).unwrap();
+    assert_eq!(Flags { carry: false, overflow: true, zero: false, sign: true, parity: false }, n.flags());
+
+    n.range_subtract_bits(whole, BufferType::from(200u32)).unwrap();
+    assert_eq!(Flags { carry: false, overflow: false, zero: true, sign: false, parity: true }, n.flags());
+    n.range_subtract_bits(whole, BufferType::from(1u32)).unwrap();
+    assert_eq!(Flags { carry: true, overflow: false, zero: false, sign: true, parity: true }, n.flags());
+
+    // flags of a range do not depend on the bits around it
+    let mut n = BincBuffer::from_str("40ff", 16).unwrap();
+    n.range_multiply_bits(BitsIndexRange(BitIndex::IndexedBit(15), BitIndex::IndexedBit(8)), BufferType::from(2u32)).unwrap();
+    assert!(!n.flags().carry && n.flags().overflow);
+    n.signed_shift_left(BitsIndexRange(BitIndex::IndexedBit(15), BitIndex::IndexedBit(8)), 1).unwrap();
+    assert!(n.flags().carry && n.flags().overflow && n.flags().zero);
+    n.unsigned_shift_right(BitsIndexRange(BitIndex::IndexedBit(7), BitIndex::LowestBit), 3);
+    assert!(n.flags().carry && !n.flags().sign);
+    n.range_and_bits(whole, BufferType::zero());
+    assert_eq!(Flags { carry: false, overflow: false, zero: true, sign: false, parity: true }, n.flags());
+}
+
+#[test]
+fn odd_width_ruler() {
+    let mut n = BincBuffer::from_str("-5", 10).unwrap();
+    n.signed_extend_to(12);
+    assert_eq!(vec!["   11 8  7       0", "-  1111  1111 1011  ..", "  0         4 3"], format!("{}", n).lines().map(|line| line.replace("\u{1b}[31m", "").replace("\u{1b}[0m", "")).collect::<Vec<_>>());
+
+    let n = BincBuffer::new(BincBufferType::Integer, true, 130).unwrap();
+    let ui = format!("{}", n);
+    assert_eq!(6, ui.lines().count());
+    assert!(ui.starts_with("   129\n"));
+    assert!(BincBuffer::new(BincBufferType::Integer, true, 0).is_err());
+}
@@ -0,0 +1,136 @@
+// binc
+// Copyright (C) 2022  Artem Ayrapetov
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Parameters of a CRC in the Rocksoft model, as the CRC catalogue of reveng lists them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CrcModel {
+    pub name: &'static str,
+    pub width: usize,
+    /// the polynomial without its highest term
+    pub poly: u64,
+    pub init: u64,
+    pub refin: bool,
+    pub refout: bool,
+    pub xorout: u64,
+}
+
+impl CrcModel {
+    pub const CRC8_SMBUS: CrcModel = CrcModel { name: "CRC-8/SMBUS", width: 8, poly: 0x07, init: 0, refin: false, refout: false, xorout: 0 };
+    pub const CRC16_ARC: CrcModel = CrcModel { name: "CRC-16/ARC", width: 16, poly: 0x8005, init: 0, refin: true, refout: true, xorout: 0 };
+    /// also known as CRC-16/CCITT-FALSE
+    pub const CRC16_IBM_3740: CrcModel = CrcModel { name: "CRC-16/IBM-3740", width: 16, poly: 0x1021, init: 0xffff, refin: false, refout: false, xorout: 0 };
+    pub const CRC16_KERMIT: CrcModel = CrcModel { name: "CRC-16/KERMIT", width: 16, poly: 0x1021, init: 0, refin: true, refout: true, xorout: 0 };
+    pub const CRC16_XMODEM: CrcModel = CrcModel { name: "CRC-16/XMODEM", width: 16, poly: 0x1021, init: 0, refin: false, refout: false, xorout: 0 };
+    pub const CRC32_ISO_HDLC: CrcModel = CrcModel { name: "CRC-32/ISO-HDLC", width: 32, poly: 0x04c11db7, init: 0xffffffff, refin: true, refout: true, xorout: 0xffffffff };
+    pub const CRC32_ISCSI: CrcModel = CrcModel { name: "CRC-32/ISCSI", width: 32, poly: 0x1edc6f41, init: 0xffffffff, refin: true, refout: true, xorout: 0xffffffff };
+    pub const CRC64_XZ: CrcModel = CrcModel {
+        name: "CRC-64/XZ", width: 64, poly: 0x42f0e1eba9ea3693, init: u64::MAX, refin: true, refout: true, xorout: u64::MAX
+    };
+
+    /// CRC of the width with the polynomial, nothing is reflected, inverted or preset.
+    pub fn custom(width: usize, poly: u64) -> Result<Self, String> {
+        if width == 0 || width > 64 {
+            return Err("CRC can be from 1 to 64 bits wide".to_owned());
+        }
+        if poly == 0 || poly > mask(width) {
+            return Err(format!("The polynomial of CRC-{} must be from 1 to 0x{:x}, without the highest term", width, mask(width)));
+        }
+        Ok(Self { name: "custom", width, poly, init: 0, refin: false, refout: false, xorout: 0 })
+    }
+
+    /// Bit by bit computation, which works at any width, the bytes go in the order they are given.
+    pub fn checksum(&self, bytes: &[u8]) -> u64 {
+        let top = 1u128 << (self.width - 1);
+        let mask = mask(self.width) as u128;
+        let mut crc = self.init as u128 & mask;
+        for &byte in bytes {
+            let byte = if self.refin { byte.reverse_bits() } else { byte };
+            for i in (0..8).rev() {
+                let feedback = (crc & top != 0) ^ ((byte >> i) & 1 == 1);
+                crc = (crc << 1) & mask;
+                if feedback {
+                    crc ^= self.poly as u128;
+                }
+            }
+        }
+        if self.refout {
+            crc = crc.reverse_bits() >> (128 - self.width);
+        }
+        (crc as u64 ^ self.xorout) & mask as u64
+    }
+
+    /// Name of the preset, or the width of a custom CRC.
+    pub fn title(&self) -> String {
+        match self.name {
+            "custom" => format!("custom CRC-{}", self.width),
+            name => name.to_owned()
+        }
+    }
+
+    /// Name and parameters, for the UI.
+    pub fn describe(&self) -> String {
+        let digits = self.width.div_ceil(4);
+        format!(
+            "{}  poly 0x{:0digits$x}  init 0x{:0digits$x}  refin {}  refout {}  xorout 0x{:0digits$x}",
+            self.title(), self.poly, self.init, self.refin as u8, self.refout as u8, self.xorout
+        )
+    }
+}
+
+fn mask(width: usize) -> u64 {
+    u64::MAX >> (64 - width)
+}
+
+/// Ones' complement sum of 16 bit words as RFC 1071 computes it for IP, TCP and UDP headers,
+/// the odd byte is padded with zero.
+pub fn internet_checksum(bytes: &[u8]) -> u16 {
+    let mut sum = bytes.chunks(2)
+        .map(|word| (word[0] as u32) << 8 | *word.get(1).unwrap_or(&0) as u32)
+        .sum::<u32>();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+pub fn adler32(bytes: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD_ADLER;
+        (a, (b + a) % MOD_ADLER)
+    });
+    b << 16 | a
+}
+
+#[test]
+fn crc_check_values() {
+    let check = b"123456789";
+    assert_eq!(0xf4, CrcModel::CRC8_SMBUS.checksum(check));
+    assert_eq!(0xbb3d, CrcModel::CRC16_ARC.checksum(check));
+    assert_eq!(0x29b1, CrcModel::CRC16_IBM_3740.checksum(check));
+    assert_eq!(0x2189, CrcModel::CRC16_KERMIT.checksum(check));
+    assert_eq!(0x31c3, CrcModel::CRC16_XMODEM.checksum(check));
+    assert_eq!(0xcbf43926, CrcModel::CRC32_ISO_HDLC.checksum(check));
+    assert_eq!(0xe3069283, CrcModel::CRC32_ISCSI.checksum(check));
+    assert_eq!(0x995dc9bbdf1939fa, CrcModel::CRC64_XZ.checksum(check));
+    // CRC-5/USB
+    let crc5_usb = CrcModel { init: 0x1f, refin: true, refout: true, xorout: 0x1f, ..CrcModel::custom(5, 0x05).unwrap() };
+    assert_eq!(0x19, crc5_usb.checksum(check));
+    assert!(CrcModel::custom(8, 0x107).is_err());
+
+    assert_eq!(0x220d, internet_checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]));
+    assert_eq!(0x11e60398, adler32(b"Wikipedia"));
+}
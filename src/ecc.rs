@@ -0,0 +1,130 @@
+// binc
+// Copyright (C) 2022  Artem Ayrapetov
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+/// Hamming code of any number of data bits: the lowest bit of a codeword is position 1, check bits are at positions,
+/// which are powers of two, data bits fill the rest from the lowest one. SECDED has the overall parity bit at position 0,
+/// which is the lowest bit of its codeword, so Hamming(7,4) becomes SECDED(8,4) and 64 data bits make SECDED(72,64).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HammingCode {
+    pub data_bits: usize,
+    /// single error correction and double error detection
+    pub secded: bool,
+}
+
+/// Data of a decoded codeword and the error, which has been corrected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+    pub data: BigUint,
+    pub syndrome: usize,
+    /// bit of the codeword, which has been flipped back
+    pub corrected_bit: Option<usize>,
+}
+
+impl HammingCode {
+    /// The code of the codeword width, if there is one: 7 bits hold 4 data bits and 12 bits hold 8.
+    pub fn from_code_bits(code_bits: usize, secded: bool) -> Option<Self> {
+        (1..code_bits).map(|data_bits| Self { data_bits, secded }).find(|code| code.code_bits() == code_bits)
+    }
+
+    fn check_bits(&self) -> usize {
+        (1..).find(|r| 1usize << r > self.data_bits + r).unwrap_or(0)
+    }
+
+    pub fn code_bits(&self) -> usize {
+        self.data_bits + self.check_bits() + self.secded as usize
+    }
+
+    /// Offset of Hamming position 1 in the codeword.
+    fn first_position(&self) -> usize {
+        !self.secded as usize
+    }
+
+    /// XOR of the positions of ones, it is zero for a valid codeword.
+    fn syndrome(&self, codeword: &BigUint) -> usize {
+        (1..=self.data_bits + self.check_bits())
+            .filter(|position| codeword.bit((position - self.first_position()) as u64))
+            .fold(0, |syndrome, position| syndrome ^ position)
+    }
+
+    pub fn encode(&self, data: &BigUint) -> BigUint {
+        let mut codeword = BigUint::zero();
+        let data_positions = (1..).filter(|position: &usize| !position.is_power_of_two());
+        for (i, position) in data_positions.take(self.data_bits).enumerate() {
+            codeword.set_bit((position - self.first_position()) as u64, data.bit(i as u64));
+        }
+        let syndrome = self.syndrome(&codeword);
+        for j in 0..self.check_bits() {
+            codeword.set_bit(((1 << j) - self.first_position()) as u64, (syndrome >> j) & 1 == 1);
+        }
+        if self.secded {
+            codeword.set_bit(0, codeword.count_ones() % 2 == 1);
+        }
+        codeword
+    }
+
+    /// Corrects a single error, two errors of SECDED and errors outside of a shortened code are reported.
+    pub fn decode(&self, codeword: &BigUint) -> Result<Decoded, String> {
+        let syndrome = self.syndrome(codeword);
+        let parity_error = codeword.count_ones() % 2 == 1;
+        let error_position = match (syndrome, self.secded) {
+            (0, false) => None,
+            (0, true) if !parity_error => None,
+            (0, true) => Some(0),
+            (_, true) if !parity_error => return Err(format!("Double error, syndrome 0x{:x}, it cannot be corrected", syndrome)),
+            (syndrome, _) if syndrome > self.data_bits + self.check_bits() =>
+                return Err(format!("Syndrome 0x{:x} points outside of the codeword, more bits are wrong", syndrome)),
+            (syndrome, _) => Some(syndrome),
+        };
+        let mut codeword = codeword.clone();
+        let corrected_bit = error_position.map(|position| position - self.first_position());
+        if let Some(bit) = corrected_bit {
+            codeword.set_bit(bit as u64, !codeword.bit(bit as u64));
+        }
+        let mut data = BigUint::zero();
+        let data_positions = (1..).filter(|position: &usize| !position.is_power_of_two());
+        for (i, position) in data_positions.take(self.data_bits).enumerate() {
+            data.set_bit(i as u64, codeword.bit((position - self.first_position()) as u64));
+        }
+        Ok(Decoded { data, syndrome, corrected_bit })
+    }
+}
+
+#[test]
+fn hamming_codes() {
+    let hamming74 = HammingCode::from_code_bits(7, false).unwrap();
+    assert_eq!(4, hamming74.data_bits);
+    // d1 d2 d3 d4 = 1 0 1 1 is 0110011 as p1 p2 d1 p4 d2 d3 d4
+    assert_eq!(BigUint::from(0b1100110u32), hamming74.encode(&BigUint::from(0b1101u32)));
+    let decoded = hamming74.decode(&BigUint::from(0b1100100u32)).unwrap();
+    assert_eq!(BigUint::from(0b1101u32), decoded.data);
+    assert_eq!((2, Some(1)), (decoded.syndrome, decoded.corrected_bit));
+
+    let secded = HammingCode { data_bits: 64, secded: true };
+    assert_eq!(72, secded.code_bits());
+    assert_eq!(Some(secded), HammingCode::from_code_bits(72, true));
+    let data = BigUint::from(0x0123456789abcdefu64);
+    let codeword = secded.encode(&data);
+    assert_eq!(Decoded { data: data.clone(), syndrome: 0, corrected_bit: None }, secded.decode(&codeword).unwrap());
+    for bit in [0u64, 1, 40, 71] {
+        let decoded = secded.decode(&(&codeword ^ (BigUint::from(1u32) << bit))).unwrap();
+        assert_eq!((data.clone(), Some(bit as usize)), (decoded.data, decoded.corrected_bit));
+    }
+    assert!(secded.decode(&(&codeword ^ BigUint::from(0b110u32))).is_err());
+    assert_eq!(None, HammingCode::from_code_bits(8, false));
+}
@@ -0,0 +1,225 @@
+// binc
+// Copyright (C) 2022  Artem Ayrapetov
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::float::FloatLiteral;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{Zero, One, Signed, ToPrimitive};
+use std::cmp::Ordering;
+
+/// How a fixed point number drops the bits it cannot hold.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rounding {
+    NearestEven,
+    NearestAway,
+    TowardZero,
+    Down,
+    Up,
+}
+
+impl Rounding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rounding::NearestEven => "nearest even",
+            Rounding::NearestAway => "nearest away",
+            Rounding::TowardZero => "toward zero",
+            Rounding::Down => "down",
+            Rounding::Up => "up",
+        }
+    }
+
+    /// Rounded quotient of the division.
+    pub fn divide(&self, numerator: &BigInt, denominator: &BigInt) -> BigInt {
+        let (numerator, denominator) = if denominator.is_negative() { (-numerator, -denominator) } else { (numerator.clone(), denominator.clone()) };
+        // floored, so the remainder is never negative
+        let (quotient, remainder) = numerator.div_mod_floor(&denominator);
+        if remainder.is_zero() {
+            return quotient;
+        }
+        let round_up = match self {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::TowardZero => numerator.is_negative(),
+            Rounding::NearestEven | Rounding::NearestAway => match (&remainder * 2u32).cmp(&denominator) {
+                Ordering::Less => false,
+                Ordering::Greater => true,
+                Ordering::Equal if *self == Rounding::NearestEven => quotient.is_odd(),
+                Ordering::Equal => !numerator.is_negative(),
+            }
+        };
+        if round_up { quotient + 1 } else { quotient }
+    }
+}
+
+/// Qm.n number: an optional sign bit, then m integer bits, then n fraction bits.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FixedFormat {
+    pub signed: bool,
+    pub integer_bits: usize,
+    pub fraction_bits: usize,
+    pub rounding: Rounding,
+}
+
+impl FixedFormat {
+    pub fn new(signed: bool, integer_bits: usize, fraction_bits: usize) -> Self {
+        Self { signed, integer_bits, fraction_bits, rounding: Rounding::NearestEven }
+    }
+
+    pub fn width(&self) -> usize {
+        self.signed as usize + self.integer_bits + self.fraction_bits
+    }
+
+    /// Q notation of the format: Q7.8 or UQ16.16
+    pub fn name(&self) -> String {
+        format!("{}Q{}.{}", if self.signed { "" } else { "U" }, self.integer_bits, self.fraction_bits)
+    }
+
+    /// The bits as an integer, which is the value multiplied by 2^n.
+    fn scaled_value(&self, bits: &BigUint) -> BigInt {
+        if self.signed && bits.bit(self.width() as u64 - 1) {
+            BigInt::from(bits.clone()) - (BigInt::one() << self.width())
+        } else {
+            BigInt::from(bits.clone())
+        }
+    }
+
+    /// The lowest bits of two's complement representation of the scaled value.
+    fn bits_of(&self, scaled_value: &BigInt) -> BigUint {
+        let modulus = BigInt::one() << self.width();
+        scaled_value.mod_floor(&modulus).magnitude().clone()
+    }
+
+    fn one(&self) -> BigInt {
+        BigInt::one() << self.fraction_bits
+    }
+
+    /// Rounded product as an integer, which is the value multiplied by 2^n, it may not fit the format.
+    pub fn scaled_product(&self, multiplicand: &BigUint, multiplier: &BigUint) -> BigInt {
+        let product = self.scaled_value(multiplicand) * self.scaled_value(multiplier);
+        self.rounding.divide(&product, &self.one())
+    }
+
+    pub fn divide(&self, dividend: &BigUint, divisor: &BigUint) -> BigUint {
+        let dividend = self.scaled_value(dividend) << self.fraction_bits;
+        self.bits_of(&self.rounding.divide(&dividend, &self.scaled_value(divisor)))
+    }
+
+    /// Integer in fixed point format, the integer is two's complement bits as wide as the format.
+    pub fn scale_integer(&self, bits: &BigUint) -> BigUint {
+        self.bits_of(&(self.scaled_value(bits) << self.fraction_bits))
+    }
+
+    /// Rounds the literal to the format, a number out of its range is an error.
+    pub fn quantize(&self, literal: &FloatLiteral) -> Result<BigUint, String> {
+        let scaled_value = match literal {
+            FloatLiteral::Finite { negative, mantissa, exponent, radix } => {
+                let power = BigInt::from(*radix).pow(exponent.unsigned_abs() as u32);
+                let mantissa = BigInt::from(mantissa.clone()) << self.fraction_bits;
+                let mantissa = if *negative { -mantissa } else { mantissa };
+                if *exponent >= 0 {
+                    mantissa * power
+                } else {
+                    self.rounding.divide(&mantissa, &power)
+                }
+            }
+            FloatLiteral::Epsilon { negative: true } => -BigInt::one(),
+            FloatLiteral::Epsilon { negative: false } => BigInt::one(),
+            _ => return Err("Fixed point numbers have no infinity and NaN".to_owned())
+        };
+        let (min, max) = if self.signed {
+            (-(BigInt::one() << (self.width() - 1)), (BigInt::one() << (self.width() - 1)) - 1)
+        } else {
+            (BigInt::zero(), (BigInt::one() << self.width()) - 1)
+        };
+        if scaled_value < min || scaled_value > max {
+            return Err(format!(
+                "The number is out of range of {}, it must be from {} to {}",
+                self.name(), self.format_scaled_value(&min), self.format_scaled_value(&max)
+            ));
+        }
+        Ok(self.bits_of(&scaled_value))
+    }
+
+    pub fn decode(&self, bits: &BigUint) -> f64 {
+        self.scaled_value(bits).to_f64().unwrap_or(f64::NAN) * 2f64.powi(-(self.fraction_bits as i32))
+    }
+
+    /// Exact decimal value of the bits, every fixed point number has a finite decimal fraction.
+    pub fn format_value(&self, bits: &BigUint) -> String {
+        self.format_scaled_value(&self.scaled_value(bits))
+    }
+
+    fn format_scaled_value(&self, scaled_value: &BigInt) -> String {
+        // value / 2^n == value * 5^n / 10^n
+        let digits = (scaled_value.magnitude() * BigUint::from(5u32).pow(self.fraction_bits as u32)).to_str_radix(10);
+        let digits = format!("{:0>1$}", digits, self.fraction_bits + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.fraction_bits);
+        let fraction = fraction.trim_end_matches('0');
+        format!(
+            "{}{}{}{}",
+            if scaled_value.is_negative() { "-" } else { "" },
+            integer, if fraction.is_empty() { "" } else { "." }, fraction
+        )
+    }
+
+    /// Format, value and resolution of the number, for the UI.
+    pub fn describe(&self, bits: &BigUint) -> String {
+        format!(
+            "{}  value {}  resolution {}  rounding {}",
+            self.name(), self.format_value(bits), self.format_scaled_value(&BigInt::one()), self.rounding.name()
+        )
+    }
+}
+
+#[test]
+fn fixed_rounding() {
+    let divide = |rounding: Rounding, numerator: i32, denominator: i32| rounding.divide(&BigInt::from(numerator), &BigInt::from(denominator));
+    assert_eq!(BigInt::from(2), divide(Rounding::NearestEven, 5, 2));
+    assert_eq!(BigInt::from(4), divide(Rounding::NearestEven, 7, 2));
+    assert_eq!(BigInt::from(-2), divide(Rounding::NearestEven, -5, 2));
+    assert_eq!(BigInt::from(3), divide(Rounding::NearestAway, 5, 2));
+    assert_eq!(BigInt::from(-3), divide(Rounding::NearestAway, 5, -2));
+    assert_eq!(BigInt::from(-2), divide(Rounding::TowardZero, -5, 2));
+    assert_eq!(BigInt::from(-3), divide(Rounding::Down, -5, 2));
+    assert_eq!(BigInt::from(3), divide(Rounding::Up, 5, 2));
+    assert_eq!(BigInt::from(2), divide(Rounding::NearestEven, 5, 3));
+}
+
+#[test]
+fn fixed_arithmetics() {
+    let q7_8 = FixedFormat::new(true, 7, 8);
+    let quantize = |format: FixedFormat, literal: &str| format.quantize(&FloatLiteral::parse(literal).unwrap()).unwrap();
+    assert_eq!(BigUint::from(0x0324u32), quantize(q7_8, "3.14"));
+    assert_eq!("3.140625", q7_8.format_value(&quantize(q7_8, "3.14")));
+    assert_eq!(BigUint::from(0xfe80u32), quantize(q7_8, "-1.5"));
+    assert_eq!("-1.5", q7_8.format_value(&BigUint::from(0xfe80u32)));
+    assert!(q7_8.quantize(&FloatLiteral::parse("128").unwrap()).is_err());
+    assert_eq!(BigUint::from(0x8000u32), quantize(q7_8, "-128"));
+
+    let down = FixedFormat { rounding: Rounding::Down, ..q7_8 };
+    assert_eq!(BigUint::from(0x0323u32), quantize(down, "3.14"));
+
+    // 1.5 * -2.25 == -3.375, 1.5 / -2.25 == -0.6666 which is rounded to -171/256
+    assert_eq!("-3.375", q7_8.format_scaled_value(&q7_8.scaled_product(&quantize(q7_8, "1.5"), &quantize(q7_8, "-2.25"))));
+    assert_eq!("-0.66796875", q7_8.format_value(&q7_8.divide(&quantize(q7_8, "1.5"), &quantize(q7_8, "-2.25"))));
+
+    let q15 = FixedFormat::new(true, 0, 15);
+    assert_eq!(16, q15.width());
+    assert_eq!("Q0.15  value -1  resolution 0.000030517578125  rounding nearest even", q15.describe(&BigUint::from(0x8000u32)));
+    let uq16_16 = FixedFormat::new(false, 16, 16);
+    assert_eq!("65535.9999847412109375", uq16_16.format_value(&BigUint::from(u32::MAX)));
+    assert_eq!(BigUint::from(0x0003_0000u32), uq16_16.scale_integer(&BigUint::from(3u32)));
+}
@@ -0,0 +1,467 @@
+// binc
+// Copyright (C) 2022  Artem Ayrapetov
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use num_bigint::BigUint;
+use num_traits::{Zero, One, ToPrimitive};
+
+const F64_FRACTION_BITS: usize = 52;
+const F64_FRACTION_MASK: u64 = (1 << F64_FRACTION_BITS) - 1;
+
+/// Binary interchange format of IEEE 754: a sign bit, then exponent bits, then fraction bits.
+/// A finite format, like FP8 E4M3, has no infinity: the biggest exponent is used for numbers,
+/// only the biggest exponent with the fraction of all ones is NaN.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FloatFormat {
+    pub exponent_bits: usize,
+    pub fraction_bits: usize,
+    pub finite: bool,
+}
+
+impl FloatFormat {
+    pub const BINARY16: FloatFormat = FloatFormat { exponent_bits: 5, fraction_bits: 10, finite: false };
+    pub const BFLOAT16: FloatFormat = FloatFormat { exponent_bits: 8, fraction_bits: 7, finite: false };
+    pub const BINARY32: FloatFormat = FloatFormat { exponent_bits: 8, fraction_bits: 23, finite: false };
+    pub const BINARY64: FloatFormat = FloatFormat { exponent_bits: 11, fraction_bits: 52, finite: false };
+    /// OCP 8-bit floating point E4M3
+    pub const E4M3: FloatFormat = FloatFormat { exponent_bits: 4, fraction_bits: 3, finite: true };
+
+    /// Any format, which values can be held by f64.
+    pub fn new(exponent_bits: usize, fraction_bits: usize, finite: bool) -> Result<Self, String> {
+        if !(2..=11).contains(&exponent_bits) {
+            return Err(format!("Exponent must be from 2 to 11 bits, given {}", exponent_bits));
+        }
+        if !(1..=52).contains(&fraction_bits) {
+            return Err(format!("Mantissa must be from 1 to 52 bits, given {}", fraction_bits));
+        }
+        Ok(Self { exponent_bits, fraction_bits, finite })
+    }
+
+    pub fn from_width(width: usize) -> Result<Self, String> {
+        match width {
+            16 => Ok(Self::BINARY16),
+            32 => Ok(Self::BINARY32),
+            64 => Ok(Self::BINARY64),
+            _ => Err(format!("There is no floating point format of {} bits, only 16, 32 and 64 are known", width))
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match *self {
+            Self::BINARY16 => "binary16".to_owned(),
+            Self::BFLOAT16 => "bfloat16".to_owned(),
+            Self::BINARY32 => "binary32".to_owned(),
+            Self::BINARY64 => "binary64".to_owned(),
+            _ => format!("E{}M{}{}", self.exponent_bits, self.fraction_bits, if self.finite { "FN" } else { "" })
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        1 + self.exponent_bits + self.fraction_bits
+    }
+
+    pub fn bias(&self) -> i64 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    fn max_biased_exponent(&self) -> u64 {
+        (1 << self.exponent_bits) - 1
+    }
+
+    fn fraction_mask(&self) -> u64 {
+        (1 << self.fraction_bits) - 1
+    }
+
+    fn sign_mask(&self) -> u64 {
+        1 << (self.width() - 1)
+    }
+
+    pub fn biased_exponent(&self, bits: u64) -> u64 {
+        (bits >> self.fraction_bits) & self.max_biased_exponent()
+    }
+
+    pub fn fraction(&self, bits: u64) -> u64 {
+        bits & self.fraction_mask()
+    }
+
+    fn is_nan(&self, bits: u64) -> bool {
+        let fraction = self.fraction(bits);
+        self.biased_exponent(bits) == self.max_biased_exponent() && if self.finite { fraction == self.fraction_mask() } else { fraction != 0 }
+    }
+
+    fn is_infinite(&self, bits: u64) -> bool {
+        !self.finite && self.biased_exponent(bits) == self.max_biased_exponent() && self.fraction(bits) == 0
+    }
+
+    pub fn is_negative(&self, bits: u64) -> bool {
+        bits & self.sign_mask() != 0
+    }
+
+    /// Flips the sign bit, as IEEE 754 negation does.
+    pub fn negate(&self, bits: u64) -> u64 {
+        bits ^ self.sign_mask()
+    }
+
+    pub fn decode(&self, bits: u64) -> f64 {
+        match *self {
+            Self::BINARY64 => f64::from_bits(bits),
+            Self::BINARY32 => f32::from_bits(bits as u32) as f64,
+            _ => self.decode_generic(bits)
+        }
+    }
+
+    /// Converts the value to the nearest number of the format, ties to even.
+    pub fn encode(&self, value: f64) -> u64 {
+        match *self {
+            Self::BINARY64 => value.to_bits(),
+            Self::BINARY32 => (value as f32).to_bits() as u64,
+            _ => self.encode_generic(value)
+        }
+    }
+
+    fn decode_generic(&self, bits: u64) -> f64 {
+        let sign = if self.is_negative(bits) { -1f64 } else { 1f64 };
+        let biased_exponent = self.biased_exponent(bits);
+        let fraction = self.fraction(bits);
+        if self.is_nan(bits) {
+            let sign = if self.is_negative(bits) { 1u64 << 63 } else { 0 };
+            f64::from_bits(sign | (0x7ff << F64_FRACTION_BITS) | (fraction << (F64_FRACTION_BITS - self.fraction_bits)))
+        } else if self.is_infinite(bits) {
+            sign * f64::INFINITY
+        } else if biased_exponent == 0 {
+            sign * fraction as f64 * 2f64.powi((1 - self.bias() - self.fraction_bits as i64) as i32)
+        } else {
+            let mantissa = fraction | (1 << self.fraction_bits);
+            sign * mantissa as f64 * 2f64.powi((biased_exponent as i64 - self.bias() - self.fraction_bits as i64) as i32)
+        }
+    }
+
+    fn encode_generic(&self, value: f64) -> u64 {
+        let negative = value.is_sign_negative();
+        if value.is_nan() && self.finite {
+            return self.infinity(negative);
+        }
+        if value.is_nan() {
+            let sign = if negative { self.sign_mask() } else { 0 };
+            let payload = (value.to_bits() & F64_FRACTION_MASK) >> (F64_FRACTION_BITS - self.fraction_bits);
+            // a fraction of zero would turn NaN into infinity
+            let payload = if payload == 0 { 1 << (self.fraction_bits - 1) } else { payload };
+            return sign | (self.max_biased_exponent() << self.fraction_bits) | payload;
+        }
+        if value.is_infinite() {
+            return self.infinity(negative);
+        }
+
+        // value is mantissa * 2^exponent
+        let value_bits = value.abs().to_bits();
+        let (mantissa, exponent) = match (value_bits >> F64_FRACTION_BITS) as i64 {
+            0 => (value_bits & F64_FRACTION_MASK, -1074),
+            biased => ((value_bits & F64_FRACTION_MASK) | (1 << F64_FRACTION_BITS), biased - 1075)
+        };
+        self.encode_parts(negative, &BigUint::from(mantissa), exponent, false)
+    }
+
+    /// Infinity, or NaN for a finite format.
+    fn infinity(&self, negative: bool) -> u64 {
+        let sign = if negative { self.sign_mask() } else { 0 };
+        let fraction = if self.finite { self.fraction_mask() } else { 0 };
+        sign | (self.max_biased_exponent() << self.fraction_bits) | fraction
+    }
+
+    /// Quiet or signaling NaN with the payload in the lowest bits of the fraction.
+    pub fn nan(&self, negative: bool, quiet: bool, payload: u64) -> Result<u64, String> {
+        if self.finite {
+            return if quiet && payload == 0 {
+                Ok(self.infinity(negative))
+            } else {
+                Err(format!("{} has only one NaN, it has no payload", self.name()))
+            };
+        }
+        let quiet_bit = 1 << (self.fraction_bits - 1);
+        if payload >= quiet_bit {
+            return Err(format!("NaN payload 0x{:x} does not fit into {} bits of fraction", payload, self.fraction_bits - 1));
+        }
+        if !quiet && payload == 0 {
+            return Err("Signaling NaN must have non-zero payload".to_owned());
+        }
+        Ok(self.infinity(negative) | if quiet { quiet_bit } else { 0 } | payload)
+    }
+
+    /// Rounds mantissa * 2^exponent to the format, ties to even.
+    /// `sticky` tells there are more non-zero bits below the mantissa, it is used only if
+    /// the mantissa has at least two bits more than the format can hold.
+    fn encode_parts(&self, negative: bool, mantissa: &BigUint, exponent: i64, sticky: bool) -> u64 {
+        let sign = if negative { self.sign_mask() } else { 0 };
+        if mantissa.is_zero() {
+            return sign;
+        }
+        let magnitude = mantissa.bits() as i64 - 1 + exponent;
+        let fraction_bits = self.fraction_bits as i64;
+        let smallest_quantum = 1 - self.bias() - fraction_bits;
+        let mut quantum = (magnitude - fraction_bits).max(smallest_quantum);
+        let mut significand = if exponent >= quantum {
+            mantissa << (exponent - quantum) as usize
+        } else {
+            shift_right_round_half_even(mantissa, (quantum - exponent) as usize, sticky)
+        };
+        if significand.bits() > self.fraction_bits as u64 + 1 {
+            // rounding carried into the next binade
+            significand >>= 1;
+            quantum += 1;
+        }
+        let significand = significand.to_u64().unwrap_or(0);
+
+        let biased_exponent = if significand >> self.fraction_bits == 0 {
+            0
+        } else {
+            (quantum + self.bias() + fraction_bits) as u64
+        };
+        let overflow = if self.finite {
+            biased_exponent > self.max_biased_exponent()
+                || (biased_exponent == self.max_biased_exponent() && significand & self.fraction_mask() == self.fraction_mask())
+        } else {
+            biased_exponent >= self.max_biased_exponent()
+        };
+        if overflow {
+            self.infinity(negative)
+        } else {
+            sign | (biased_exponent << self.fraction_bits) | (significand & self.fraction_mask())
+        }
+    }
+
+    /// Sign, exponent and fraction fields of the number and its value, for the UI.
+    pub fn describe(&self, bits: u64) -> String {
+        let biased_exponent = self.biased_exponent(bits);
+        let fraction = self.fraction(bits);
+        let value = self.decode(bits);
+        let quiet_bit = 1 << (self.fraction_bits - 1);
+        let (unbiased_exponent, class) = if self.is_nan(bits) {
+            (String::from("-"), if self.finite { " (NaN)" } else if fraction & quiet_bit != 0 { " (quiet NaN)" } else { " (signaling NaN)" })
+        } else if self.is_infinite(bits) {
+            (String::from("-"), " (infinity)")
+        } else if biased_exponent == 0 {
+            ((1 - self.bias()).to_string(), if fraction == 0 { " (zero)" } else { " (subnormal)" })
+        } else {
+            ((biased_exponent as i64 - self.bias()).to_string(), "")
+        };
+        format!(
+            "sign {}  exponent {} biased, {} unbiased  fraction 0x{:x}  value {}{}",
+            if self.is_negative(bits) { '-' } else { '+' },
+            biased_exponent, unbiased_exponent, fraction, self.format_value(value), class
+        )
+    }
+
+    pub fn format_value(&self, value: f64) -> String {
+        let abs = value.abs();
+        if value.is_nan() || value.is_infinite() || abs == 0f64 || (1e-5..1e16).contains(&abs) {
+            if *self == Self::BINARY32 { format!("{}", value as f32) } else { format!("{}", value) }
+        } else if *self == Self::BINARY32 {
+            format!("{:e}", value as f32)
+        } else {
+            format!("{:e}", value)
+        }
+    }
+}
+
+fn shift_right_round_half_even(value: &BigUint, shift: usize, sticky: bool) -> BigUint {
+    let quotient = value >> shift;
+    let remainder = value - (&quotient << shift);
+    let half = BigUint::one() << (shift - 1);
+    if remainder > half || (remainder == half && (sticky || quotient.bit(0))) {
+        quotient + 1u32
+    } else {
+        quotient
+    }
+}
+
+/// Floating point number as it is written, its bits depend on the format it is encoded to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FloatLiteral {
+    /// mantissa * radix^exponent, radix is 2 or 10
+    Finite { negative: bool, mantissa: BigUint, exponent: i64, radix: u32 },
+    Infinity { negative: bool },
+    NaN { negative: bool, quiet: bool, payload: u64 },
+    /// the difference between 1 and the next number of the format
+    Epsilon { negative: bool },
+}
+
+impl FloatLiteral {
+    /// Parses decimal literals like "-3.14" or "1e-5" and hexadecimal ones like "0x1.8p3".
+    pub fn parse(literal: &str) -> Result<Self, String> {
+        let (negative, body) = match literal.strip_prefix('-') {
+            Some(body) => (true, body),
+            None => (false, literal)
+        };
+        let (radix, digits_radix, body, exponent_letters) = match body.get(..2) {
+            Some("0x") | Some("0X") | Some("0h") | Some("0H") => (2, 16, &body[2..], ['p', 'P']),
+            _ => (10, 10, body, ['e', 'E'])
+        };
+        let (digits, exponent) = match body.find(&exponent_letters[..]) {
+            Some(i) => (&body[..i], parse_exponent(&body[i + 1..])?),
+            None => (body, 0)
+        };
+        let (integer_digits, fraction_digits) = match digits.find('.') {
+            Some(i) => (&digits[..i], &digits[i + 1..]),
+            None => (digits, "")
+        };
+        let all_digits = format!("{}{}", integer_digits, fraction_digits);
+        let mantissa = match BigUint::parse_bytes(all_digits.as_bytes(), digits_radix) {
+            Some(mantissa) if !all_digits.contains(['+', '-']) => mantissa,
+            _ => return Err(format!("Bad floating point literal '{}'", literal))
+        };
+        let digit_weight = if radix == 2 { 4 } else { 1 };
+        let exponent = exponent - digit_weight * fraction_digits.len() as i64;
+
+        // stop before numbers get too big to compute, they are infinity or zero for any format
+        let magnitude = if radix == 2 { mantissa.bits() as i64 } else { mantissa.to_str_radix(10).len() as i64 } + exponent;
+        if !mantissa.is_zero() && magnitude > if radix == 2 { 5000 } else { 1300 } {
+            Ok(FloatLiteral::Infinity { negative })
+        } else if mantissa.is_zero() || magnitude < if radix == 2 { -1200 } else { -400 } {
+            Ok(FloatLiteral::Finite { negative, mantissa: BigUint::zero(), exponent: 0, radix })
+        } else {
+            Ok(FloatLiteral::Finite { negative, mantissa, exponent, radix })
+        }
+    }
+
+    pub fn encode(&self, format: FloatFormat) -> Result<u64, String> {
+        match self {
+            FloatLiteral::Finite { negative, mantissa, exponent, radix: 2 } =>
+                Ok(format.encode_parts(*negative, mantissa, *exponent, false)),
+            FloatLiteral::Finite { negative, mantissa, exponent, .. } if *exponent >= 0 =>
+                Ok(format.encode_parts(*negative, &(mantissa * BigUint::from(10u32).pow(*exponent as u32)), 0, false)),
+            FloatLiteral::Finite { negative, mantissa, exponent, .. } => {
+                // mantissa / 10^-exponent with two extra bits for rounding and the sticky bit
+                let divisor = BigUint::from(10u32).pow(-*exponent as u32);
+                let shift = (format.fraction_bits as i64 + 3 + divisor.bits() as i64 - mantissa.bits() as i64).max(0) as usize;
+                let dividend = mantissa << shift;
+                let quotient = &dividend / &divisor;
+                let sticky = !(dividend % divisor).is_zero();
+                Ok(format.encode_parts(*negative, &quotient, -(shift as i64), sticky))
+            }
+            FloatLiteral::Infinity { .. } if format.finite => Err(format!("{} has no infinity", format.name())),
+            FloatLiteral::Infinity { negative } => Ok(format.infinity(*negative)),
+            FloatLiteral::NaN { negative, quiet, payload } => format.nan(*negative, *quiet, *payload),
+            FloatLiteral::Epsilon { negative } =>
+                Ok(format.encode_parts(*negative, &BigUint::one(), -(format.fraction_bits as i64), false)),
+        }
+    }
+
+    /// The integer part of the number as a decimal literal.
+    pub fn truncate(&self) -> Result<String, String> {
+        let (negative, integer) = match self {
+            FloatLiteral::Finite { negative, mantissa, exponent, radix: 2 } => (*negative, if *exponent >= 0 {
+                mantissa << *exponent as usize
+            } else {
+                mantissa >> -*exponent as usize
+            }),
+            FloatLiteral::Finite { negative, mantissa, exponent, .. } => (*negative, if *exponent >= 0 {
+                mantissa * BigUint::from(10u32).pow(*exponent as u32)
+            } else {
+                mantissa / BigUint::from(10u32).pow(-*exponent as u32)
+            }),
+            FloatLiteral::Epsilon { .. } => (false, BigUint::zero()),
+            _ => return Err("Infinity and NaN cannot be set to an integer".to_owned())
+        };
+        Ok(format!("{}{}", if negative && !integer.is_zero() { "-" } else { "" }, integer))
+    }
+}
+
+fn parse_exponent(exponent: &str) -> Result<i64, String> {
+    let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Bad exponent '{}' of floating point literal", exponent));
+    }
+    // exponents which do not fit are huge anyway
+    let limit = if exponent.starts_with('-') { -1_000_000 } else { 1_000_000 };
+    Ok(exponent.parse::<i64>().unwrap_or(limit).clamp(-1_000_000, 1_000_000))
+}
+
+#[test]
+fn float_generic_encoding_matches_binary32() {
+    let values = [
+        0f64, -0f64, 1f64, -1f64, 0.1, std::f64::consts::PI, 1e-40, 1e-45, 7e-46, 1e-46, 3.4028235e38, 3.4028236e38, 1e39,
+        f64::INFINITY, f64::NEG_INFINITY, 16777217f64, 16777219f64, 1.401298464324817e-45, 5.877471754111438e-39,
+    ];
+    for &value in values.iter() {
+        let expected = (value as f32).to_bits() as u64;
+        assert_eq!(expected, FloatFormat::BINARY32.encode_generic(value), "encoding {}", value);
+        let decoded = FloatFormat::BINARY32.decode_generic(expected);
+        assert_eq!((value as f32) as f64, decoded, "decoding {}", value);
+    }
+    let nan = FloatFormat::BINARY32.encode_generic(f64::NAN);
+    assert!(f32::from_bits(nan as u32).is_nan());
+    assert!(FloatFormat::BINARY32.decode_generic(0x7fc0_0001).is_nan());
+}
+
+#[test]
+fn float_fields() {
+    let pi = FloatFormat::BINARY32.encode(std::f64::consts::PI);
+    assert_eq!(0x4049_0fdb, pi);
+    assert_eq!(128, FloatFormat::BINARY32.biased_exponent(pi));
+    assert_eq!(0x49_0fdb, FloatFormat::BINARY32.fraction(pi));
+    assert_eq!("sign +  exponent 128 biased, 1 unbiased  fraction 0x490fdb  value 3.1415927", FloatFormat::BINARY32.describe(pi));
+    assert_eq!("sign -  exponent 0 biased, -1022 unbiased  fraction 0x0  value -0 (zero)", FloatFormat::BINARY64.describe(1 << 63));
+    assert_eq!(FloatFormat::BINARY64.encode(-2.5), FloatFormat::BINARY64.negate(FloatFormat::BINARY64.encode(2.5)));
+}
+
+#[test]
+fn float_literal_encoding() {
+    let encode = |literal: &str, format: FloatFormat| FloatLiteral::parse(literal).unwrap().encode(format).unwrap();
+    assert_eq!(0x4048_f5c3, encode("3.14", FloatFormat::BINARY32));
+    assert_eq!(0x3fb9_9999_9999_999a, encode("0.1", FloatFormat::BINARY64));
+    assert_eq!(0x4140_0000, encode("0x1.8p3", FloatFormat::BINARY32));
+    assert_eq!(0x8000_0000, encode("-0", FloatFormat::BINARY32));
+    assert_eq!(0x1, encode("1e-45", FloatFormat::BINARY32));
+    assert_eq!(0x7f80_0000, encode("1e39", FloatFormat::BINARY32));
+    assert_eq!(0x4b80_0000, encode("16777217.0", FloatFormat::BINARY32));
+    // just above the midpoint between 1 and the next float, rounding through f64 would give 1
+    assert_eq!(0x3f80_0001, encode("1.00000005960464477550", FloatFormat::BINARY32));
+    assert_eq!(f64::INFINITY.to_bits(), encode("1e999999999999999999999", FloatFormat::BINARY64));
+    assert_eq!(0x7fc0_0123, FloatLiteral::NaN { negative: false, quiet: true, payload: 0x123 }.encode(FloatFormat::BINARY32).unwrap());
+    assert_eq!(0x3400_0000, FloatLiteral::Epsilon { negative: false }.encode(FloatFormat::BINARY32).unwrap());
+    assert!(FloatLiteral::NaN { negative: false, quiet: false, payload: 0 }.encode(FloatFormat::BINARY32).is_err());
+    assert!(FloatLiteral::parse("1e").is_err());
+
+    assert_eq!("-25", FloatLiteral::parse("-2.59e1").unwrap().truncate().unwrap());
+    assert_eq!("12", FloatLiteral::parse("0x1.8p3").unwrap().truncate().unwrap());
+    assert_eq!("0", FloatLiteral::parse("-0.5").unwrap().truncate().unwrap());
+    assert!(FloatLiteral::Infinity { negative: false }.truncate().is_err());
+}
+
+#[test]
+fn float_minifloat_formats() {
+    let encode = |literal: &str, format: FloatFormat| FloatLiteral::parse(literal).unwrap().encode(format).unwrap();
+    assert_eq!(0x7bff, encode("65504", FloatFormat::BINARY16));
+    assert_eq!(0x7c00, encode("65520", FloatFormat::BINARY16));
+    assert_eq!(0x0001, encode("5.96e-8", FloatFormat::BINARY16));
+    assert_eq!(0x4049, encode("3.14", FloatFormat::BFLOAT16));
+    assert_eq!(1.5, FloatFormat::BINARY16.decode(0x3e00));
+
+    // E4M3 has no infinity, its biggest exponent holds numbers up to 448, overflow is NaN
+    assert_eq!(0x7e, encode("448", FloatFormat::E4M3));
+    assert_eq!(448f64, FloatFormat::E4M3.decode(0x7e));
+    assert_eq!(0x7f, encode("480", FloatFormat::E4M3));
+    assert!(FloatFormat::E4M3.decode(0xff).is_nan());
+    assert_eq!(0x7f, FloatFormat::E4M3.encode(f64::INFINITY));
+    assert!(FloatLiteral::Infinity { negative: false }.encode(FloatFormat::E4M3).is_err());
+    assert_eq!("sign +  exponent 15 biased, 8 unbiased  fraction 0x6  value 448", FloatFormat::E4M3.describe(0x7e));
+    assert_eq!("sign -  exponent 15 biased, - unbiased  fraction 0x7  value NaN (NaN)", FloatFormat::E4M3.describe(0xff));
+
+    let e5m2 = FloatFormat::new(5, 2, false).unwrap();
+    assert_eq!(0x7b, encode("57344", e5m2));
+    assert_eq!(0x7c, encode("61440", e5m2));
+    assert_eq!("E5M2", e5m2.name());
+    assert!(FloatFormat::new(1, 2, false).is_err());
+}
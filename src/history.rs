@@ -54,7 +54,7 @@ impl History {
     }
 
     pub fn forward(&mut self) -> BincBuffer {
-        if self.forward_list.len() == 0 {
+        if self.forward_list.is_empty() {
             self.backward_list.back().unwrap().clone()
         } else {
             let number = self.forward_list.pop_back().unwrap();
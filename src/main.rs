@@ -20,6 +20,11 @@ mod syntax;
 mod buffer;
 mod operators;
 mod history;
+mod float;
+mod fixed;
+mod checksum;
+mod ecc;
+mod text;
 
 use buffer::{BincBuffer, BincBufferType};
 
@@ -28,7 +33,7 @@ use rustyline::config::Configurer;
 use rustyline::error::ReadlineError;
 use rustyline::{Editor, Event, Cmd, KeyEvent, EventHandler};
 use syntax::parse;
-use operators::{HandlerResult};
+use operators::{HandlerResult, resolve_named_access};
 use crate::operators::OperationResult;
 use crate::history::History;
 use clap::{Arg, command};
@@ -38,7 +43,8 @@ use smallvec::{smallvec};
 use std::process::exit;
 use crossterm_cursor::cursor;
 
-fn print_ui(number: &BincBuffer) {
+/// Prints the buffer and returns how many lines were printed.
+fn print_ui(number: &BincBuffer) -> u16 {
     let line = format!(
         "{0:>1$} {2:>3$} {4:>5$} {6:>7$}",
         number.to_string_as_char(), 3,
@@ -47,18 +53,27 @@ fn print_ui(number: &BincBuffer) {
         number.to_string_prefixed(8), number.number_of_digits_in_radix(8) + 2,
     );
 
+    let buffer_ui = format!("{}", number);
     println!();
     println!("{}", line.color(Color::Green));
-    println!("{}", number);
+    let mut lines_printed = 2;
+    if let Some(format_fields) = number.to_string_format_fields() {
+        println!("{}", format_fields.color(Color::Green));
+        lines_printed += 1;
+    }
+    println!("{}", buffer_ui);
+    println!("{}", number.to_string_flags().color(Color::Green));
+    lines_printed + 1 + buffer_ui.lines().count() as u16
 }
 
 type Executor = dyn FnOnce(&mut BincBuffer) -> OperationResult;
 
-fn generate_executor(command: &str) -> Result<Box<Executor>, String> {
-    match parse(command) {
+fn generate_executor(command: &str, number_type: BincBufferType) -> Result<Box<Executor>, String> {
+    match parse(command, number_type) {
         Ok((left_operand_source, operator_handler, right_operand_source)) => {
             Ok(
                 Box::new(move |main_buffer: &mut BincBuffer| {
+                    let (left_operand_source, right_operand_source) = resolve_named_access(main_buffer, left_operand_source, right_operand_source)?;
                     operator_handler(main_buffer, left_operand_source, right_operand_source)
                 })
             )
@@ -79,10 +94,9 @@ fn interactive_routine(history_size: usize, append_output: bool) {
     // TODO SHIFT+LEFT/SHIFT+RIGHT and Ctrl-u/Ctrl-r (bash intercepts this) - undo/redo
     cli_editor.bind_sequence(Event::KeySeq(smallvec![KeyEvent::ctrl('Q')]), EventHandler::from(Cmd::EndOfFile));
 
-    let mut lines_printed = 0u16;
     loop {
-        print_ui(&main_buffer);
-        lines_printed = 6;
+        // and one more line for the prompt
+        let mut lines_printed = print_ui(&main_buffer) + 1;
 
         let input = cli_editor.readline("(binc) ");
         match input {
@@ -98,7 +112,7 @@ fn interactive_routine(history_size: usize, append_output: bool) {
                     } else {
                         cli_editor.add_history_entry(command);
                     }
-                    match generate_executor(command) {
+                    match generate_executor(command, main_buffer.buffer_type()) {
                         Ok(executor) => {
                             match executor(&mut main_buffer) {
                                 Ok((handler_result, optional_message)) => {
@@ -118,7 +132,7 @@ fn interactive_routine(history_size: usize, append_output: bool) {
                                     lines_printed += 1;
                                 }
                             }
-                            trace!("buffer: {}, size {}, bits 0b{:b} ", main_buffer.signed(), main_buffer.max_size(), main_buffer.to_u128());
+                            trace!("buffer: {}, size {}, bits {} ", main_buffer.signed(), main_buffer.max_size(), main_buffer.to_string_prefixed(2));
                         }
                         Err(err_msg) => {
                             println!("parsing error: {}", err_msg);
@@ -158,7 +172,7 @@ fn not_interactive_routine(commands: &str, format: &str, prepend0: bool) -> Stri
 
     trace!("interactive: got commands: '{}'", commands);
     let command_list = commands.split(";").collect::<Vec<_>>();
-    if (command_list.is_empty()) {
+    if command_list.is_empty() {
         debug!("nothing to do");
         exit(1);
     }
@@ -167,7 +181,7 @@ fn not_interactive_routine(commands: &str, format: &str, prepend0: bool) -> Stri
             debug!("skipping empty command");
             continue;
         }
-        match generate_executor(command) {
+        match generate_executor(command, main_buffer.buffer_type()) {
             Ok(executor) => {
                 match executor(&mut main_buffer) {
                     Ok((handler_result, _)) => {
@@ -187,7 +201,7 @@ fn not_interactive_routine(commands: &str, format: &str, prepend0: bool) -> Stri
                         exit(1);
                     }
                 }
-                trace!("buffer: {}, size {}, bits 0b{:b} ", main_buffer.signed(), main_buffer.max_size(), main_buffer.to_u128());
+                trace!("buffer: {}, size {}, bits {} ", main_buffer.signed(), main_buffer.max_size(), main_buffer.to_string_prefixed(2));
             }
             Err(err_msg) => {
                 eprintln!("parsing error: {}", err_msg);
@@ -197,7 +211,7 @@ fn not_interactive_routine(commands: &str, format: &str, prepend0: bool) -> Stri
     }
     trace!("command executing is done, format is '{}', prepend is '{}'", format, prepend0);
     // FIXME refactor format parsing
-    let output = match format {
+    match format {
         "0x" | "0h" => main_buffer.to_string(16, true, prepend0),
         "x" | "h" => main_buffer.to_string(16, false, prepend0),
         "0d" => main_buffer.to_string(10, true, prepend0),
@@ -206,9 +220,9 @@ fn not_interactive_routine(commands: &str, format: &str, prepend0: bool) -> Stri
         "o" => main_buffer.to_string(8, false, prepend0),
         "0b" => main_buffer.to_string(2, true, prepend0),
         "b" => main_buffer.to_string(2, false, prepend0),
+        "f" => main_buffer.to_string_as_float(),
         _ => "".to_owned()
-    };
-    output
+    }
 }
 
 fn main() {
@@ -242,7 +256,7 @@ fn main() {
             .short('f')
             .takes_value(true)
             .default_value("0b")
-            .help("Specifies prefix for output: b -binary, o - octal, d - decimal, h - hexadecimal. 0f - with prefix, where f is (b|o|d|h)+."))
+            .help("Specifies prefix for output: b -binary, o - octal, d - decimal, h - hexadecimal, f - value of floating point number. 0f - with prefix, where f is (b|o|d|h)+."))
         .arg(Arg::new("prepend0")
             .long("prepend0")
             .short('p')
@@ -286,6 +300,18 @@ fn main() {
     }
 }
 
+/// Runs `;`-separated commands on an unsigned 32-bit buffer as the interactive mode does, a command, which fails,
+/// leaves the buffer as it is; gives the result of the last command and the buffer.
+#[cfg(test)]
+fn run_commands(commands: &str) -> (Result<Option<String>, String>, BincBuffer) {
+    let mut buffer = BincBuffer::new(BincBufferType::Integer, false, 32).unwrap();
+    let mut result = Ok(None);
+    for command in commands.split(';') {
+        result = generate_executor(command, buffer.buffer_type()).and_then(|executor| executor(&mut buffer)).map(|(_, message)| message);
+    }
+    (result, buffer)
+}
+
 #[test]
 pub fn empty_commands_dont_cause_any_errors() {
     assert!(not_interactive_routine("", "", false).is_empty());
@@ -297,3 +323,456 @@ pub fn empty_commands_dont_cause_any_errors() {
     assert_eq!("1", not_interactive_routine("1;", "d", false));
     assert_eq!("1", not_interactive_routine("1;", "d", false));
 }
+
+#[test]
+pub fn float_arithmetics() {
+    assert_eq!("6.2831855", not_interactive_routine("float32;0x40490fdb;*2", "f", false));
+    assert_eq!("0.6666667", not_interactive_routine("float32;0x3f800000;+1;/3", "f", false));
+    assert_eq!("1.4142135623730951", not_interactive_routine("float64;0;+2;root", "f", false));
+    assert_eq!("-2", not_interactive_routine("float32;0x3f800000;!;-1", "f", false));
+    assert_eq!("inf", not_interactive_routine("float32;0x3f800000;/0", "f", false));
+    assert_eq!("NaN", not_interactive_routine("float32;0;/0", "f", false));
+    // a part of a float is an integer
+    assert_eq!("0x40000000", not_interactive_routine("float32;0x3f800000;[30:23]+1", "0x", false));
+}
+
+#[test]
+pub fn float_literals() {
+    assert_eq!("3.14", not_interactive_routine("float32;3.14", "f", false));
+    assert_eq!("0x3fb999999999999a", not_interactive_routine("float64;0.1", "0x", false));
+    assert_eq!("12", not_interactive_routine("float32;0x1.8p3", "f", false));
+    assert_eq!("0x80000000", not_interactive_routine("float32;=-0", "0x", false));
+    assert_eq!("-inf", not_interactive_routine("float32;=-inf", "f", false));
+    assert_eq!("0x7fc00123", not_interactive_routine("float32;=NaN(0x123)", "0x", false));
+    assert_eq!("0x7f800005", not_interactive_routine("float32;=sNaN(5)", "0x", false));
+    assert_eq!("0x7fc00000", not_interactive_routine("float32;=NaN(0)", "0x", false));
+    assert_eq!(Err("Signaling NaN must have non-zero payload".to_owned()), run_commands("float32;=sNaN(0)").0);
+    assert_eq!("0x3f800001", not_interactive_routine("float32;=1.0;+eps", "0x", false));
+    assert_eq!("2.220446049250313e-16", not_interactive_routine("float64;eps", "f", false));
+    assert_eq!("3.75", not_interactive_routine("float32;1.5;*2.5", "f", false));
+    // an integer buffer takes the integer part
+    assert_eq!("-25", not_interactive_routine("int32;=-2.59e1", "d", false));
+    assert_eq!("-128", not_interactive_routine("int8;=-1.28e2", "d", false));
+    // a negative literal keeps its sign bit, -200 is wider than 8 bits
+    assert_eq!("-200", not_interactive_routine("int16;=-200", "d", false));
+    assert_eq!(Err("The number is out of range of 8 bits, it must be from -128 to 127".to_owned()), run_commands("int8;signed;=1e400").0);
+    assert_eq!(Err("The number is out of range of 8 bits, it must be from -128 to 127".to_owned()), run_commands("int8;signed;=-1.5e2").0);
+    assert_eq!(Err("The number is out of range of 8 bits, it must be from 0 to 255".to_owned()), run_commands("int8;=2.56e2").0);
+    assert_eq!("0x40000001", not_interactive_routine("float32;=2.5;[22:0]=1.5", "0x", false));
+}
+
+#[test]
+pub fn float_minifloat_formats() {
+    assert_eq!("0x3c00", not_interactive_routine("float16;1.0", "0x", false));
+    assert_eq!("0.0999755859375", not_interactive_routine("float16;0.1", "f", false));
+    assert_eq!("3.140625", not_interactive_routine("bfloat16;3.14", "f", false));
+    assert_eq!("448", not_interactive_routine("e4m3;449.0", "f", false));
+    assert_eq!("NaN", not_interactive_routine("e4m3;448.0;*2", "f", false));
+    assert_eq!("inf", not_interactive_routine("e5m2;57344.0;*2", "f", false));
+    assert_eq!("0x42", not_interactive_routine("e5m2;1.0;*3", "0x", false));
+    assert_eq!("2", not_interactive_routine("e4m3;1.0;e+=1", "f", false));
+    assert_eq!("14", not_interactive_routine("e3m2;14.0", "f", false));
+    assert_eq!("24", not_interactive_routine("e3m2fn;=24.0", "f", false));
+    assert_eq!("NaN", not_interactive_routine("e3m2fn;=28.0", "f", false));
+}
+
+#[test]
+pub fn fixed_point_arithmetics() {
+    assert_eq!("3.140625", not_interactive_routine("q7.8;3.14", "f", false));
+    assert_eq!("0x324", not_interactive_routine("q7.8;3.14", "0x", false));
+    assert_eq!("3.13671875", not_interactive_routine("q7.8;rdn;3.14", "f", false));
+    assert_eq!("-3.375", not_interactive_routine("q7.8;1.5;*-2.25", "f", false));
+    assert_eq!("-0.66796875", not_interactive_routine("q7.8;1.5;/-2.25", "f", false));
+    assert_eq!("-0.6640625", not_interactive_routine("q7.8;rtz;1.5;/-2.25", "f", false));
+    assert_eq!("0.25", not_interactive_routine("q15;0.5;*0.5", "f", false));
+    assert_eq!("65535.75", not_interactive_routine("uq16.16;65535.5;+0.25", "f", false));
+    // an integer operand is a value, but assignment of an integer sets bits
+    assert_eq!("2.5", not_interactive_routine("q7.8;1.5;+1", "f", false));
+    assert_eq!("0.00390625", not_interactive_routine("q7.8;=1", "f", false));
+    assert_eq!("1.00390625", not_interactive_routine("q7.8;1.0;[7:0]=1.5", "f", false));
+    assert_eq!(Err("Integer power works only with integers".to_owned()), run_commands("q7.8;=1.0;pow 2").0);
+    assert_eq!(Err("Integer root works only with integers".to_owned()), run_commands("q7.8;=2.0;root").0);
+    // a part of a fixed point number is an integer
+    assert_eq!("0x309", not_interactive_routine("q7.8;=3.0;[7:0]=3;[7:0]pow 2", "0x", false));
+}
+
+#[test]
+pub fn float_named_access() {
+    assert_eq!("3", not_interactive_routine("float32;1.5;e+=1", "f", false));
+    assert_eq!("0.5", not_interactive_routine("float64;1.0;e-1", "f", false));
+    assert_eq!("2", not_interactive_routine("float32;3.14;f=0", "f", false));
+    assert_eq!("-3.14", not_interactive_routine("float32;3.14;s^=1", "f", false));
+    assert_eq!("0x80", not_interactive_routine("float32;-2.0;[7:0]=e;[31:8]=0", "0x", false));
+    assert_eq!("1.0000001", not_interactive_routine("float32;1.0;f|=1", "f", false));
+}
+
+#[test]
+pub fn carry_flag_operand() {
+    assert_eq!("1", not_interactive_routine("int8;255;+1;=c", "d", false));
+    assert_eq!("0", not_interactive_routine("int8;254;+1;=c", "d", false));
+    // borrow
+    assert_eq!("6", not_interactive_routine("int8;5;-6;[7:0]=5;+c", "d", false));
+    assert_eq!("1", not_interactive_routine("int8;0x81;<<1;=c", "d", false));
+}
+
+#[test]
+pub fn signed_comparisons() {
+    assert_eq!(Ok(Some("no".to_owned())), run_commands("signed;-1;[]>1").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("signed;-1;[]<1").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("unsigned;-1;[]>1").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("signed;-1;[]>=-1").0);
+    assert_eq!(Ok(Some("no".to_owned())), run_commands("signed;-1;[]!=-1").0);
+    assert_eq!(Ok(Some("no".to_owned())), run_commands("signed;5;[]<=4").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("signed;[7:0]=-1;[7:0]<0").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("signed;[7:0]=-1;[7:0]==-1").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("float32;NaN;[]!=NaN").0);
+    assert_eq!(Ok(Some("no".to_owned())), run_commands("float32;NaN;[]==NaN").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("float32;-1;[]<0.5").0);
+    assert_eq!(Ok(Some("yes".to_owned())), run_commands("float32;-0;[]==0").0);
+}
+
+#[test]
+pub fn signed_division() {
+    assert_eq!("-3", not_interactive_routine("-7;/2", "d", false));
+    assert_eq!("-1", not_interactive_routine("-7;%2", "d", false));
+    assert_eq!("-4", not_interactive_routine("fdiv;-7;/2", "d", false));
+    assert_eq!("1", not_interactive_routine("fdiv;-7;%2", "d", false));
+    assert_eq!("-1", not_interactive_routine("fdiv;7;%-2", "d", false));
+    assert_eq!("-4", not_interactive_routine("-8;>>1", "d", false));
+    assert_eq!("2147483644", not_interactive_routine("unsigned;-8;>>1", "d", false));
+    assert_eq!("0x7f", not_interactive_routine("unsigned;[7:0]=0xfe;[7:0]/2;[31:8]=0", "0x", false));
+}
+
+#[test]
+pub fn odd_integer_widths() {
+    assert_eq!("-1", not_interactive_routine("int12;-1", "d", false));
+    assert_eq!("-2048", not_interactive_routine("int12;2047;+1", "d", false));
+    assert_eq!("4", not_interactive_routine("int12;5;+-1", "d", false));
+    assert_eq!("5", not_interactive_routine("int7;-5;!", "d", false));
+    assert_eq!("-64", not_interactive_routine("int7;[6]=1", "d", false));
+    assert_eq!("16777215", not_interactive_routine("int24;-1;unsigned", "d", false));
+    assert_eq!("-2", not_interactive_routine("int12;-4;>>1", "d", false));
+}
+
+#[test]
+pub fn overflow_modes() {
+    assert_eq!("-56", not_interactive_routine("int8;100;+100", "d", false));
+    assert_eq!("127", not_interactive_routine("int8;saturate;100;+100", "d", false));
+    assert_eq!("-128", not_interactive_routine("int8;saturate;-100;-100", "d", false));
+    assert_eq!("255", not_interactive_routine("int8;unsigned;saturate;200;+100", "d", false));
+    assert_eq!("0", not_interactive_routine("int8;unsigned;saturate;5;-6", "d", false));
+    assert_eq!("127", not_interactive_routine("int8;saturate;20;*20", "d", false));
+    assert_eq!("-128", not_interactive_routine("int8;saturate;-3;pow5", "d", false));
+    assert_eq!("127", not_interactive_routine("int8;saturate;0x40;<<1", "d", false));
+    assert_eq!("127", not_interactive_routine("int8;checked;100;+27", "d", false));
+    assert_eq!("-56", not_interactive_routine("int8;checked;wrap;100;+100", "d", false));
+    // a negative literal is the same number in every mode, also in an unsigned buffer
+    for mode in ["wrap", "saturate", "checked"] {
+        assert_eq!("199", not_interactive_routine(&format!("unsigned;int8;{};=200;+ -1", mode), "d", false));
+        assert_eq!("201", not_interactive_routine(&format!("unsigned;int8;{};=200;- -1", mode), "d", false));
+        assert_eq!("199", not_interactive_routine(&format!("unsigned;int8;{};=200;-1", mode), "d", false));
+    }
+    assert_eq!("0", not_interactive_routine("unsigned;int8;saturate;=200;*-1", "d", false));
+    assert_eq!("0", not_interactive_routine("unsigned;int8;saturate;=5;+ -6", "d", false));
+
+    // an overflow in checked mode leaves the number and the flags as they are
+    let (_, before) = run_commands("int8;signed;checked;100;+20");
+    assert!(run_commands("int8;signed;checked;100;+20;+100").0.is_err());
+    let (result, after) = run_commands("int8;signed;checked;100;+20;+100;<<2");
+    assert!(result.is_err());
+    assert_eq!("120", after.to_string(10, false, false));
+    assert_eq!(before.flags(), after.flags());
+}
+
+#[test]
+pub fn integer_roots_and_logarithms() {
+    assert_eq!("18446744073709551615", not_interactive_routine("int128;-1;unsigned;isqrt", "d", false));
+    assert_eq!("4611686018427387904", not_interactive_routine("int128;unsigned;[]=0x4000000000000000;*0x4000000000000000;isqrt", "d", false));
+    assert_eq!("6981463658331", not_interactive_routine("int128;-1;unsigned;root3", "d", false));
+    assert_eq!("0", not_interactive_routine("0;root", "d", false));
+    assert_eq!("-3", not_interactive_routine("-27;root3", "d", false));
+    assert_eq!("3", not_interactive_routine("1000;ilog10", "d", false));
+    assert_eq!("2", not_interactive_routine("999;ilog10", "d", false));
+    assert_eq!("10", not_interactive_routine("1024;ilog2", "d", false));
+    assert_eq!("9", not_interactive_routine("1023;ilog", "d", false));
+    assert_eq!("1", not_interactive_routine("255;ilog 16", "d", false));
+    assert_eq!("1233", not_interactive_routine("int4096;unsigned;-1;ilog10", "d", false));
+    assert_eq!(Err("Integer logarithm works only with integers".to_owned()), run_commands("q7.8;=4.0;ilog2").0);
+    assert_eq!(Err("Integer square root works only with integers".to_owned()), run_commands("q7.8;=4.0;isqrt").0);
+    assert_eq!(Err("Integer square root works only with integers".to_owned()), run_commands("float32;=4.0;isqrt").0);
+}
+
+#[test]
+pub fn byte_order() {
+    assert_eq!("0x78563412", not_interactive_routine("0x12345678;bswap", "0x", false));
+    assert_eq!("0x56781234", not_interactive_routine("0x12345678;hswap", "0x", false));
+    assert_eq!("0x5566778811223344", not_interactive_routine("int64;0x1122334455667788;wswap", "0x", false));
+    assert_eq!("0x12347856", not_interactive_routine("0x12345678;[15:0]bswap", "0x", false));
+
+    assert_eq!(Ok(Some("big endian: 12 34 56 78  little endian: 78 56 34 12".to_owned())), run_commands("0x12345678;mem").0);
+    assert_eq!(Ok(Some("big endian: 04 56  little endian: 56 04".to_owned())), run_commands("0x12345678;[19:8]mem").0);
+    assert!(run_commands("0x12345678;[11:0]bswap").0.is_err());
+}
+
+#[test]
+pub fn bit_manipulation() {
+    assert_eq!("0x4", not_interactive_routine("0b10110100;blsi", "0x", false));
+    assert_eq!("0xb0", not_interactive_routine("0b10110100;blsr", "0x", false));
+    assert_eq!("0x0", not_interactive_routine("0;blsr", "0x", false));
+    assert_eq!("0xb0", not_interactive_routine("0b1011;pdep 0b11110000", "0x", false));
+    assert_eq!("0xf0", not_interactive_routine("0xf0f0;pext 0xff00", "0x", false));
+    assert_eq!("0x56", not_interactive_routine("0x12345678;bextr 0x0808", "0x", false));
+    assert_eq!("0x4567", not_interactive_routine("0x12345678;bextr 0x1004", "0x", false));
+    // the bits from the field up move up, `=` overwrites the field
+    assert_eq!("0x3456ff78", not_interactive_routine("0x12345678;[15:8]bfi 0x1ff", "0x", false));
+    assert_eq!("0x1234ff78", not_interactive_routine("0x12345678;[15:8]=0xff", "0x", false));
+    assert_eq!("0x2345678a", not_interactive_routine("0x12345678;[3:0]bfi 0xa", "0x", false));
+    assert_eq!("0x3f800000", not_interactive_routine("float32;[]bfi 0x3f800000", "0x", false));
+
+    assert_eq!(Ok(Some("27".to_owned())), run_commands("0x10;lzcnt").0);
+    assert_eq!(Ok(Some("4".to_owned())), run_commands("0x10;tzcnt").0);
+    assert_eq!(Ok(Some("32".to_owned())), run_commands("0;tzcnt").0);
+    assert_eq!(Ok(Some("3".to_owned())), run_commands("0x10;[7:0]lzcnt").0);
+}
+
+#[test]
+pub fn carry_arithmetics() {
+    // 64 bit sums and differences a word at a time
+    assert_eq!("0x200000000", not_interactive_routine("int64;[31:0]=0xffffffff;[63:32]=1;[31:0]+1;[63:32]adc 0", "0x", false));
+    assert_eq!("0xfffffffe", not_interactive_routine("int64;[31:0]=0xffffffff;[63:32]=0;[31:0]+0xffffffff;[63:32]adc 0xffffffff", "0x", false));
+    assert_eq!("0x1ffffffff", not_interactive_routine("int64;[31:0]=0;[63:32]=2;[31:0]-1;[63:32]sbb 0", "0x", false));
+    assert_eq!("0x5", not_interactive_routine("int8;0x81;<<1;rcl", "0x", false));
+    assert_eq!("0x80", not_interactive_routine("int8;unsigned;0x80;+0x80;rcr", "0x", false));
+    assert_eq!("0x80", not_interactive_routine("int8;unsigned;1;rcr;rcr", "0x", false));
+    assert_eq!("0x81", not_interactive_routine("int8;unsigned;0xff;+1;[7:0]=0x81;rcl 9", "0x", false));
+}
+
+#[test]
+pub fn widening_multiplication_and_divmod() {
+    assert_eq!("0xfffffffffffffffe0000000000000001", not_interactive_routine("int128;unsigned;[63:0]=-1;[63:0]mulw 0xffffffffffffffff", "0x", false));
+    assert_eq!("0x1", not_interactive_routine("int64;unsigned;-1;mulw 0xffffffffffffffff", "0x", false));
+    assert_eq!("-3", not_interactive_routine("int8;-7;divmod 2", "d", false));
+
+    assert_eq!(Ok(Some("high 0xfffffffffffffffe".to_owned())), run_commands("int64;unsigned;-1;mulw 0xffffffffffffffff").0);
+    assert_eq!(Ok(Some("high 0xffffffffffffffff".to_owned())), run_commands("int64;signed;-1;mulw 2").0);
+    assert_eq!(Ok(None), run_commands("int64;[31:0]=0x10000;[31:0]mulw 0x10000").0);
+    assert_eq!(Ok(Some("quotient -3 remainder -1".to_owned())), run_commands("int8;signed;-7;divmod 2").0);
+    assert_eq!(Ok(Some("quotient -4 remainder 1".to_owned())), run_commands("int8;signed;fdiv;-7;divmod 2").0);
+    assert_eq!(Ok(Some("quotient 35 remainder 5".to_owned())), run_commands("int8;unsigned;250;divmod 7").0);
+}
+
+#[test]
+pub fn modular_arithmetics() {
+    assert_eq!("6", not_interactive_routine("48;gcd 18", "d", false));
+    assert_eq!("2", not_interactive_routine("-4;gcd 6", "d", false));
+    assert_eq!("12", not_interactive_routine("4;lcm 6", "d", false));
+    assert_eq!("5", not_interactive_routine("3;modinv 7", "d", false));
+    assert_eq!("5", not_interactive_routine("modulus 7;3;modinv", "d", false));
+    assert_eq!("688423210", not_interactive_routine("int64;modulus 1000000007;2;modpow 1000", "d", false));
+
+    let p25519 = "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+    assert_eq!(
+        "0x3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7",
+        not_interactive_routine(&format!("int256;2;modinv {}", p25519), "0x", false)
+    );
+    assert_eq!(
+        "0x3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7",
+        not_interactive_routine(&format!("int256;modulus {};2;modpow -1", p25519), "0x", false)
+    );
+    assert_eq!(
+        "0x378820c7b263b3f574f44b193a10c785912a83406f24dffcbab8592466979353",
+        not_interactive_routine(&format!("int256;modulus {};0x1234567890abcdef;modpow 65537", p25519), "0x", false)
+    );
+
+    assert_eq!(Err("6 has no inverse modulo 4, their greatest common divisor is 2".to_owned()), run_commands("6;modinv 4").0);
+    assert_eq!(Err("No modulus is set, set it with `modulus M` first".to_owned()), run_commands("2;modpow 3").0);
+    assert_eq!(Err("The modulus must be a positive number".to_owned()), run_commands("modulus 0").0);
+    assert_eq!(Ok(Some("modulus 97".to_owned())), run_commands("modulus 97;modulus").0);
+}
+
+#[test]
+pub fn galois_field_arithmetics() {
+    assert_eq!("0x5", not_interactive_routine("3;clmul 3", "0x", false));
+    assert_eq!("0x55555555555555555555555555555555", not_interactive_routine("int128;[63:0]=-1;[63:0]clmul 0xffffffffffffffff", "0x", false));
+
+    assert_eq!("0xc1", not_interactive_routine("int8;unsigned;gf 0x11b;0x57;*0x83", "0x", false));
+    assert_eq!("0xca", not_interactive_routine("int8;unsigned;gf 0x11b;1;/0x53", "0x", false));
+    assert_eq!("0x1", not_interactive_routine("int8;unsigned;gf 0x11b;3;pow 255", "0x", false));
+    assert_eq!("0x1a", not_interactive_routine("int8;unsigned;gf 0x11b;gf;0xd;*2", "0x", false));
+    // the divisor is reduced by the polynomial, 0x148 is 0x53 in the field
+    assert_eq!("0xca", not_interactive_routine("int8;unsigned;gf 0x11b;int16;1;/0x148", "0x", false));
+
+    let ghash = "int128;gf 0x100000000000000000000000000000087;0x66e94bd4ef8a2c3b884cfa59ca342b2e";
+    assert_eq!("0x519fa38ac731568e9c1eb21731167f1c", not_interactive_routine(&format!("{};*0x0388dace60b6a392f328c2b971b2fe78", ghash), "0x", false));
+    assert_eq!(
+        "0x66e94bd4ef8a2c3b884cfa59ca342b2e",
+        not_interactive_routine(&format!("{};*0x0388dace60b6a392f328c2b971b2fe78;/0x0388dace60b6a392f328c2b971b2fe78", ghash), "0x", false)
+    );
+
+    assert_eq!(Ok(Some("high 0x1".to_owned())), run_commands("-1;clmul 3").0);
+    assert_eq!(Err("0x2 has no inverse in the field, the polynomial 0x100 is not irreducible".to_owned()), run_commands("gf 0x100;1;/2").0);
+    assert_eq!(Err("GF(2^8) needs a range of 8 bits at least".to_owned()), run_commands("gf 0x11b;[3:0]*2").0);
+    assert_eq!(Err("Cannot divide by 0".to_owned()), run_commands("int8;gf 0x11b;int16;=3;/0x11b").0);
+}
+
+#[test]
+pub fn checksums() {
+    let check = "0x313233343536373839";
+    assert_eq!(Ok(Some("CRC-32/ISO-HDLC 0xcbf43926".to_owned())), run_commands(&format!("int128;{};[71:0]crc", check)).0);
+    assert_eq!(Ok(Some("CRC-32/ISCSI 0xe3069283".to_owned())), run_commands(&format!("int128;crc32c;{};[71:0]crc", check)).0);
+    assert_eq!(Ok(Some("CRC-16/IBM-3740 0x29b1".to_owned())), run_commands(&format!("int128;crc16ccitt;{};[71:0]crc", check)).0);
+    assert_eq!(Ok(Some("CRC-8/SMBUS 0xf4".to_owned())), run_commands(&format!("int128;crc8;{};[71:0]crc", check)).0);
+    assert_eq!(Ok(Some("CRC-64/XZ 0x995dc9bbdf1939fa".to_owned())), run_commands(&format!("int128;crc64;{};[71:0]crc", check)).0);
+    assert_eq!(
+        Ok(Some("custom CRC-5 0x19".to_owned())),
+        run_commands(&format!("int128;crc5 0x05;crcinit 0x1f;crcrefin 1;crcrefout 1;crcxorout 0x1f;{};[71:0]crc", check)).0
+    );
+    assert_eq!(
+        Ok(Some("custom CRC-16  poly 0x1021  init 0xffff  refin 0  refout 0  xorout 0x0000".to_owned())),
+        run_commands("crc16 0x1021;crcinit 0xffff").0
+    );
+    assert_eq!(Ok(Some("Internet checksum 0x220d".to_owned())), run_commands("int64;0x0001f203f4f5f6f7;[63:0]csum").0);
+    assert_eq!(Ok(Some("Adler-32 0x11e60398".to_owned())), run_commands("int128;0x57696b697065646961;[71:0]adler32").0);
+
+    assert_eq!("0xcbf43926000000313233343536373839", not_interactive_routine(&format!("int128;unsigned;{};[127:96]crc [71:0]", check), "0x", false));
+    assert_eq!(Err("There is no CRC-7 preset, give the polynomial, like crc7 0x1021".to_owned()), run_commands("crc7").0);
+    assert_eq!(Err("Checksum is computed over whole bytes, the range of 4 bits is not".to_owned()), run_commands("[3:0]crc").0);
+    assert_eq!(Err("The checksum of 32 bits does not fit the range of 16 bits".to_owned()), run_commands("int128;[15:0]crc [127:64]").0);
+}
+
+#[test]
+pub fn parity_and_hamming_codes() {
+    assert_eq!("0x10b", not_interactive_routine("int16;unsigned;0x0b;[8]parity [7:0]", "0x", false));
+    assert_eq!("0xb", not_interactive_routine("int16;unsigned;0x0b;[8]oddparity [7:0]", "0x", false));
+    assert_eq!("0x66d", not_interactive_routine("int16;unsigned;0b1101;[10:4]hamenc [3:0]", "0x", false));
+    assert_eq!("0x62d", not_interactive_routine("int16;unsigned;0x620;[3:0]hamdec [10:4]", "0x", false));
+    assert_eq!(
+        "0x123456789abcdef",
+        not_interactive_routine("int256;unsigned;0x0123456789abcdef;[135:64]secenc [63:0];[100]~;[63:0]=0;[63:0]secdec [135:64];[135:64]=0", "0x", false)
+    );
+
+    assert_eq!(Ok(Some("ones 3  even parity 1".to_owned())), run_commands("0x0b;[7:0]parity").0);
+    assert_eq!(Ok(Some("ones 3  odd parity 0".to_owned())), run_commands("0x0b;[7:0]oddparity").0);
+    assert_eq!(Ok(Some("syndrome 0x3  corrected bit 6".to_owned())), run_commands("0x620;[3:0]hamdec [10:4]").0);
+    assert_eq!(Ok(Some("syndrome 0x0  no error".to_owned())), run_commands("0x66d;[3:0]hamdec [10:4]").0);
+    assert_eq!(Ok(Some("syndrome 0x0  corrected bit 4".to_owned())), run_commands("0b1101;[11:4]secenc [3:0];[4]=1;[3:0]secdec [11:4]").0);
+    assert_eq!(Err("Double error, syndrome 0x1, it cannot be corrected".to_owned()), run_commands("0b1101;[11:4]secenc [3:0];[5:4]~;[3:0]secdec [11:4]").0);
+    assert_eq!(Err("4 data bits make a codeword of 7 bits, the range has 8".to_owned()), run_commands("[11:4]hamenc [3:0]").0);
+    assert_eq!(Err("There is no Hamming code of 8 bits".to_owned()), run_commands("[3:0]hamdec [11:4]").0);
+}
+
+#[test]
+pub fn signed_representations() {
+    assert_eq!("0xfa", not_interactive_routine("int8;ones;=-5;unsigned", "0x", false));
+    assert_eq!("-5", not_interactive_routine("int8;ones;=-5", "d", false));
+    assert_eq!("-0", not_interactive_routine("int8;unsigned;=0xff;ones", "d", false));
+    assert_eq!("0x85", not_interactive_routine("int8;signmag;=-5;unsigned", "0x", false));
+    assert_eq!("5", not_interactive_routine("int8;signmag;=-5;!", "d", false));
+    assert_eq!("-0", not_interactive_routine("int8;signmag;0;!", "d", false));
+    assert_eq!("0x7b", not_interactive_routine("int8;excess;=-5;unsigned", "0x", false));
+    assert_eq!("-128", not_interactive_routine("int8;excess;=-128;!", "d", false));
+    assert_eq!("-12", not_interactive_routine("int12;unsigned;0x7f3;excess 2047", "d", false));
+    assert_eq!("0xf", not_interactive_routine("int8;negabinary;=-5;unsigned", "0x", false));
+    assert_eq!("-5", not_interactive_routine("int8;unsigned;0xf;negabinary", "d", false));
+    assert_eq!("0x5", not_interactive_routine("int8;negabinary;=-5;!;unsigned", "0x", false));
+    assert_eq!("-5", not_interactive_routine("int8;ones;=-5;signed;ones", "d", false));
+    assert_eq!("-6", not_interactive_routine("int8;ones;=-5;signed", "d", false));
+
+    assert_eq!(Ok(None), run_commands("int8;signed;signmag").0);
+    assert_eq!(Err("The number does not fit 8 bits of sign-magnitude, it must be from -127 to 127".to_owned()), run_commands("int8;signed;signmag;=-128").0);
+    assert_eq!(Ok(None), run_commands("int8;signed;signmag;=-128;negabinary").0);
+    let (result, buffer) = run_commands("int8;signed;signmag;=-128;negabinary;100");
+    assert_eq!(Err("The number does not fit 8 bits of negabinary, it must be from -170 to 85".to_owned()), result);
+    assert_eq!(Some("negabinary".to_owned()), buffer.to_string_format_fields());
+    assert!(format!("{}", buffer).lines().nth(1).unwrap().starts_with('+'));
+}
+
+#[test]
+pub fn encodings() {
+    assert_eq!("0x1234", not_interactive_routine("1234;bcd", "0x", false));
+    assert_eq!("1234", not_interactive_routine("0x1234;unbcd", "d", false));
+    assert_eq!("0b1011", not_interactive_routine("0b1101;gray", "0b", false));
+    assert_eq!("13", not_interactive_routine("0b1011;ungray", "d", false));
+    assert_eq!("1", not_interactive_routine("signed;=-1;zigzag", "d", false));
+    assert_eq!("-3", not_interactive_routine("signed;5;unzigzag", "d", false));
+    assert_eq!("0x268ee5", not_interactive_routine("624485;leb128", "0x", false));
+    assert_eq!("624485", not_interactive_routine("0x268ee5;unleb128", "d", false));
+    assert_eq!("-123456", not_interactive_routine("signed;=-123456;sleb128;unsleb128", "d", false));
+
+    assert_eq!(Ok(Some("bytes e5 8e 26".to_owned())), run_commands("624485;leb128").0);
+    assert_eq!(Ok(Some("bytes c0 bb 78".to_owned())), run_commands("signed;=-123456;sleb128").0);
+    assert_eq!(Ok(Some("bytes e5 8e 26".to_owned())), run_commands("0x00268ee5;unleb128").0);
+    assert_eq!(Err("0x1a is not packed BCD, the digit at bits 3:0 is greater than 9".to_owned()), run_commands("0x1a;unbcd").0);
+    assert_eq!(Err("123456789 takes 36 bits of packed BCD, the range has 32".to_owned()), run_commands("123456789;bcd").0);
+    assert_eq!(Err("0x80808080 is not LEB128, every byte of the range has the continuation bit".to_owned()), run_commands("0x80808080;unleb128").0);
+}
+
+#[test]
+pub fn text_encodings() {
+    assert_eq!("0x5fc3", not_interactive_routine("='心'", "0x", false));
+    assert_eq!("0xe5bf83", not_interactive_routine("0xe5bf83;utf8", "0x", false));
+
+    let view = |commands: &str| {
+        let (result, buffer) = run_commands(commands);
+        assert_eq!(Ok(None), result);
+        (buffer.to_string_format_fields().unwrap_or_default(), buffer.to_string_as_char())
+    };
+    assert_eq!(("UTF-8 e5 bf 83  \"心\"".to_owned(), "'心'".to_owned()), view("0xe5bf83;utf8"));
+    assert_eq!(("UTF-8 c0 af  bytes 0..1 are an overlong encoding of U+002F".to_owned(), " ? ".to_owned()), view("0xc0af;utf8"));
+    assert_eq!("UTF-8 ed b2 80  bytes 0..2 encode the surrogate U+DC80", view("0xedb280;utf8").0);
+    assert_eq!(("UTF-16BE d8 3d de 00  \"😀\"".to_owned(), "'😀'".to_owned()), view("0xd83dde00;utf16be"));
+    assert_eq!("UTF-16LE 3d d8 3d d8  unit 0 0xd83d is an unpaired surrogate", view("0x3dd83dd8;utf16le").0);
+    assert_eq!("codepoint U+03BB  UTF-8 ce bb  \"λ\"", view("='λ';codepoint").0);
+    assert_eq!("codepoint 0xd800  U+D800 is a surrogate, not a character", view("0xd800;codepoint").0);
+}
+
+#[test]
+pub fn string_literals() {
+    assert_eq!("0x52494646", not_interactive_routine("\"RIFF\"", "0x", false));
+    assert_eq!("0x46464952", not_interactive_routine("=\"RIFF\" le", "0x", false));
+    assert_eq!("0x68656c6c6f20776f726c64", not_interactive_routine("int32;\"hello world\"", "0x", false));
+    assert_eq!("0x89504e470d0a1a0a", not_interactive_routine("unsigned;\"\\x89PNG\\r\\n\\x1a\\n\"", "0x", false));
+    assert_eq!("0xf09f9880", not_interactive_routine("unsigned;\"\\u{1F600}\"", "0x", false));
+    assert_eq!("0xf09f9880", not_interactive_routine("unsigned;\"😀\"", "0x", false));
+    assert_eq!("0x4142", not_interactive_routine("int64;[15:0]=\"AB\"", "0x", false));
+
+    let ascii = |commands: &str| {
+        let (result, buffer) = run_commands(commands);
+        result.unwrap();
+        format!("{}", buffer).lines().nth(1).unwrap().replace("\u{1b}[31m", "").replace("\u{1b}[0m", "")
+    };
+    assert!(ascii("\"RIFF\"").ends_with("  RIFF"));
+    assert!(ascii("\"WAVEfmt \"").ends_with("  WAVEfmt "));
+    assert_eq!(Err("The string takes 32 bits, the range has 8".to_owned()), run_commands("[7:0]=\"RIFF\"").0);
+    assert_eq!(Err("Unknown escape sequence \\q".to_owned()), run_commands("\"\\q\"").0);
+    assert_eq!(Err("\\u{D800} is not a Unicode scalar value".to_owned()), run_commands("\"\\u{D800}\"").0);
+    assert_eq!(Err("The literal is not closed with \"".to_owned()), run_commands("\"RIFF").0);
+    assert_eq!(Err("String literal is empty".to_owned()), run_commands("\"\"").0);
+}
+
+#[test]
+pub fn named_ranges() {
+    assert_eq!("0x8c000280", not_interactive_routine("unsigned;let opcode = [31:26];let rd = [11:7];opcode = 0x23;rd = 5", "0x", false));
+    assert_eq!("0x28000", not_interactive_routine("unsigned;let rd = [11:7];let rs1 = [19:15];rd = 5;rd <> rs1", "0x", false));
+    assert_eq!("0xa", not_interactive_routine("let rd = [11:7];let dest = rd;dest = 10;[11:7] = rd;=rd", "0x", false));
+    // a name only starts with a keyword, a float name or a format
+    for name in ["interrupt", "info", "power", "epsilon", "e2", "q1"] {
+        assert_eq!("0x33", not_interactive_routine(&format!("let {0} = [7:4];{0} = 3;{0} <> [3:0];{0} = [3:0]", name), "0x", false));
+    }
+
+    let (message, buffer) = run_commands("let opcode = [31:26];let rd = [11:7];let imm = [31:20];ranges");
+    assert_eq!(Ok(Some("opcode [31:26]  rd [11:7]  imm [31:20]".to_owned())), message);
+    // imm starts at the same bit as opcode, so it goes to the line above
+    assert_eq!(Some("   imm"), format!("{}", buffer).lines().next());
+    let (message, buffer) = run_commands("let opcode = [31:26];let rd = [11:7];let rd;ranges");
+    assert_eq!((Ok(Some("opcode [31:26]".to_owned())), Some("   opcode")), (message, format!("{}", buffer).lines().next()));
+    assert_eq!(Ok(Some("no named ranges".to_owned())), run_commands("ranges").0);
+
+    assert_eq!(Err("opcod is not a named range, `let opcod = [i:j]` names one".to_owned()), run_commands("let opcode = [31:26];opcod = 1").0);
+    assert_eq!(Err("A range is named with a word, which is not a keyword, like `let opcode = [31:26]`".to_owned()), run_commands("let rnd = [3:0]").0);
+    assert_eq!(Err("A range is named with a word, which is not a keyword, like `let opcode = [31:26]`".to_owned()), run_commands("let int16 = [3:0]").0);
+    assert_eq!(Err("A range is named with a word, which is not a keyword, like `let opcode = [31:26]`".to_owned()), run_commands("let nan = [3:0]").0);
+    assert_eq!(Err("[40:32] is out of the buffer of 32 bits".to_owned()), run_commands("let high = [40:32]").0);
+    assert_eq!(Err("rd can be a name of a range only, like `let rd = [31:26]`".to_owned()), run_commands("let rd = 5").0);
+    assert_eq!(Err("wide is [31:16], it is out of the buffer of 16 bits".to_owned()), run_commands("let wide = [31:16];int16;wide = 1").0);
+}
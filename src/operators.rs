@@ -14,11 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::syntax::{LeftOperandSource, RightOperandSource};
-use crate::buffer::{BincBuffer, BitsIndexRange, BitIndex, BincBufferType};
+use crate::syntax::{LeftOperandSource, RightOperandSource, NamedAccess};
+use crate::buffer::{BincBuffer, BitsIndexRange, BitIndex, BincBufferType, BufferType, Division, OverflowMode, Representation};
+use crate::float::FloatFormat;
+use crate::checksum::{CrcModel, internet_checksum, adler32};
+use crate::text::TextEncoding;
 use colored::{Colorize, Color};
-use rand::random;
+use num_traits::ToPrimitive;
 use log::trace;
+use std::cmp::Ordering;
 
 pub type OperationResult = Result<(HandlerResult, Option<String>), String>;
 
@@ -37,34 +41,73 @@ use HandlerResult::Redo;
 
 pub type Operator = fn(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult;
 
+/// Replaces named accessors with the ranges they mean for the current format of the buffer.
+pub fn resolve_named_access(buffer: &BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> Result<(LeftOperandSource, RightOperandSource), String> {
+    let left = match left {
+        LeftOperandSource::NamedAccessSource(NamedAccess::Carry) => return Err("Carry flag can be only read, it is set by arithmetic operations".to_owned()),
+        left @ LeftOperandSource::NamedAccessSource(NamedAccess::NewRange(_)) => left,
+        LeftOperandSource::NamedAccessSource(named_access) => LeftOperandSource::RangeSource(named_range(buffer, named_access)?),
+        left => left
+    };
+    let right = match right {
+        RightOperandSource::NamedAccessSource(NamedAccess::Carry) =>
+            RightOperandSource::DirectSource(BincBuffer::from_str(if buffer.flags().carry { "1" } else { "0" }, 10)?),
+        RightOperandSource::NamedAccessSource(named_access) => RightOperandSource::RangeSource(named_range(buffer, named_access)?),
+        right => right
+    };
+    Ok((left, right))
+}
+
+fn named_range(buffer: &BincBuffer, named_access: NamedAccess) -> Result<BitsIndexRange, String> {
+    if let NamedAccess::Range(name) = &named_access {
+        return buffer.named_range(name);
+    }
+    let format = match buffer.float_format() {
+        Some(format) => format,
+        None => return Err("Sign, exponent and fraction are accessible only in a floating point buffer, use float32 or float64".to_owned())
+    };
+    match named_access {
+        NamedAccess::Sign => Ok(BitsIndexRange(BitIndex::HighestBit, BitIndex::HighestBit)),
+        NamedAccess::Exponent => Ok(BitsIndexRange(BitIndex::IndexedBit(format.width() - 2), BitIndex::IndexedBit(format.fraction_bits))),
+        NamedAccess::Fraction => Ok(BitsIndexRange(BitIndex::IndexedBit(format.fraction_bits - 1), BitIndex::LowestBit)),
+        _ => Err(format!("{:?} cannot be accessed", named_access))
+    }
+}
+
 pub fn operator_show_help(_: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
     let mut buffer = String::with_capacity(400);
 
     buffer.push_str(&"X operator Y:".color(Color::BrightGreen).to_string());
-    buffer.push_str(" >> << + - >>> * / % > < ^ & | <<~ ~>> == = <> pow root cnt\r\n");
+    buffer.push_str(" >> << + - >>> * / % > < >= <= ^ & | <<~ ~>> == != = <> pow root ilog cnt pdep pext bextr bfi adc sbb rcl rcr mulw clmul divmod gcd lcm modinv modpow crc csum adler32 parity oddparity hamenc hamdec secenc secdec\r\n");
 
     buffer.push_str(&"operator X:".color(Color::BrightGreen).to_string());
-    buffer.push_str(" ! ~ rnd shf rev\r\n");
+    buffer.push_str(" ! ~ rnd shf rev isqrt bswap hswap wswap mem blsi blsr lzcnt tzcnt bcd unbcd gray ungray zigzag unzigzag leb128 unleb128 sleb128 unsleb128\r\n");
 
     buffer.push_str(&"X and Y can be:".color(Color::BrightGreen).to_string());
-    buffer.push_str(" [] [i] [:] [i:] [:j] [i:j] c; e f\r\n");
+    buffer.push_str(" [] [i] [:] [i:] [:j] [i:j]; s e f\r\n");
 
     buffer.push_str(&"only Y can be:".color(Color::BrightGreen).to_string());
-    buffer.push_str(" 1 3.14 -0; -inf +inf NaN eps; 'a'\r\n");
+    buffer.push_str(" 1 3.14 1e-5 0x1.8p3 -0; -inf +inf NaN NaN(1) sNaN(1) eps; 'a' '\\n' '\\u{3BB}'; \"RIFF\" \"RIFF\"le \"\\x89\\u{1F600}\"; c\r\n");
 
     buffer.push_str(&"commands:".color(Color::BrightGreen).to_string());
-    buffer.push_str(" intX floatX fixedX printf signed unsigned undo redo about ?");
+    buffer.push_str(" intX floatX bfloat16 eXmY qM.N uqM.N rne rna rtz rdn rup printf signed unsigned ones signmag excess negabinary codepoint utf8 utf16le utf16be tdiv fdiv wrap saturate checked modulus gf crcN crc32c crc16ccitt crcinit crcxorout crcrefin crcrefout let ranges undo redo about ?");
 
     Ok((Nonhistorical, Some(buffer)))
 }
 
 pub fn operator_assign(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut other_number) => {
-            other_number.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(other_number) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = other_number.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
+                    // integers are raw bits even for a float buffer, floating point and fixed point literals are values
+                    let bits = if !matches!(other_number.buffer_type(), BincBufferType::Integer) {
+                        buffer.operand_for_range(target_range, other_number)?
+                    } else if other_number.is_text() {
+                        buffer.text_literal_for_range(target_range, other_number)?
+                    } else {
+                        buffer.integer_literal_for_range(target_range, other_number)?
+                    };
                     trace!("operator_assign: get bits: {:b}", bits);
                     buffer.set_bits(target_range, bits);
                 },
@@ -88,13 +131,13 @@ pub fn operator_assign(buffer: &mut BincBuffer, left: LeftOperandSource, right:
 
 pub fn operator_sum(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_sum_operand) => {
-            second_sum_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_sum_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_sum_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
+                    let negative_operand = second_sum_operand.is_negative();
+                    let bits = buffer.operand_for_range(target_range, second_sum_operand)?;
                     trace!("operator_sum: get bits: {:?}", bits);
-                    buffer.range_add_bits(target_range, bits);
+                    buffer.range_add_operand(target_range, bits, negative_operand)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -102,9 +145,9 @@ pub fn operator_sum(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
                     trace!("operator_sum: get bits: {:?}", bits);
-                    buffer.range_add_bits(target_range, bits);
+                    buffer.range_add_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -117,12 +160,12 @@ pub fn operator_sum(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_sub(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_sum_operand) => {
-            second_sum_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_sum_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_sum_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    buffer.range_subtract_bits(target_range, bits);
+                    let negative_operand = second_sum_operand.is_negative();
+                    let bits = buffer.operand_for_range(target_range, second_sum_operand)?;
+                    buffer.range_subtract_operand(target_range, bits, negative_operand)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -130,8 +173,8 @@ pub fn operator_sub(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
-                    buffer.range_subtract_bits(target_range, bits);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    buffer.range_subtract_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -144,12 +187,12 @@ pub fn operator_sub(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_mul(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_sum_operand) => {
-            second_sum_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_sum_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_sum_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    buffer.range_multiply_bits(target_range, bits);
+                    let negative_operand = second_sum_operand.is_negative();
+                    let bits = buffer.operand_for_range(target_range, second_sum_operand)?;
+                    buffer.range_multiply_operand(target_range, bits, negative_operand)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -157,8 +200,8 @@ pub fn operator_mul(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
-                    buffer.range_multiply_bits(target_range, bits);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    buffer.range_multiply_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -171,13 +214,12 @@ pub fn operator_mul(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_div(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_sum_operand) => {
-            second_sum_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_sum_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_sum_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    if bits != 0u128 {
-                        buffer.range_div_bits(target_range, bits);
+                    let bits = buffer.operand_for_range(target_range, second_sum_operand)?;
+                    if !buffer.is_zero_for_range(target_range, &bits) || buffer.is_float_range(target_range) {
+                        buffer.range_div_bits(target_range, bits)?;
                     } else {
                         return Err("Cannot divide by 0".to_owned());
                     }
@@ -188,9 +230,9 @@ pub fn operator_div(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
-                    if bits != 0u128 {
-                        buffer.range_div_bits(target_range, bits);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    if !buffer.is_zero_for_range(target_range, &bits) || buffer.is_float_range(target_range) {
+                        buffer.range_div_bits(target_range, bits)?;
                     } else {
                         return Err("There are only 0 bits in given range, cannot divide by 0".to_owned())
                     }
@@ -206,12 +248,15 @@ pub fn operator_div(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_mod(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_sum_operand) => {
-            second_sum_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_sum_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_sum_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    buffer.range_mod_bits(target_range, bits);
+                    let bits = buffer.operand_for_range(target_range, second_sum_operand)?;
+                    if !buffer.is_zero_for_range(target_range, &bits) || buffer.is_float_range(target_range) {
+                        buffer.range_mod_bits(target_range, bits);
+                    } else {
+                        return Err("Cannot divide by 0".to_owned());
+                    }
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -219,8 +264,12 @@ pub fn operator_mod(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
-                    buffer.range_mod_bits(target_range, bits);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    if !buffer.is_zero_for_range(target_range, &bits) || buffer.is_float_range(target_range) {
+                        buffer.range_mod_bits(target_range, bits);
+                    } else {
+                        return Err("There are only 0 bits in given range, cannot divide by 0".to_owned())
+                    }
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -233,12 +282,11 @@ pub fn operator_mod(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_pow(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_operand) => {
-            second_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    buffer.range_pow_bits(target_range, bits);
+                    let bits = buffer.operand_for_range(target_range, second_operand)?;
+                    buffer.range_pow_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -246,8 +294,8 @@ pub fn operator_pow(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = buffer.get_bits(source_range);
-                    buffer.range_pow_bits(target_range, bits);
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    buffer.range_pow_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -256,7 +304,8 @@ pub fn operator_pow(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::Empty => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    buffer.range_pow_bits(target_range, 2);
+                    let bits = buffer.operand_for_range(target_range, BincBuffer::from_str("2", 10)?)?;
+                    buffer.range_pow_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -267,12 +316,63 @@ pub fn operator_pow(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
 
 pub fn operator_root(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(mut second_operand) => {
-            second_operand.signed_extend_to(buffer.max_size());
+        RightOperandSource::DirectSource(second_operand) => {
+            match left {
+                LeftOperandSource::RangeSource(target_range) => {
+                    let bits = buffer.operand_for_range(target_range, second_operand)?;
+                    buffer.range_root_bits(target_range, bits)?;
+                },
+                LeftOperandSource::NamedAccessSource(_) => {},
+            }
+        }
+        RightOperandSource::RangeSource(source_range) => {
+            match left {
+                LeftOperandSource::RangeSource(target_range) => {
+                    let bits = buffer.range_operand_for_range(target_range, source_range);
+                    buffer.range_root_bits(target_range, bits)?;
+                },
+                LeftOperandSource::NamedAccessSource(_) => {},
+            }
+        }
+        RightOperandSource::NamedAccessSource(_) => {},
+        RightOperandSource::Empty => {
+            match left {
+                LeftOperandSource::RangeSource(target_range) => {
+                    let bits = buffer.operand_for_range(target_range, BincBuffer::from_str("2", 10)?)?;
+                    buffer.range_root_bits(target_range, bits)?;
+                },
+                LeftOperandSource::NamedAccessSource(_) => {},
+            }
+        }
+    }
+    Ok((Historical, None))
+}
+
+pub fn operator_isqrt(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    match right {
+        RightOperandSource::DirectSource(_) => return Err("No second operand allowed!".to_owned()),
+        RightOperandSource::RangeSource(_) => return Err("No second operand allowed!".to_owned()),
+        RightOperandSource::NamedAccessSource(_) => {},
+        RightOperandSource::Empty => {
+            match left {
+                LeftOperandSource::RangeSource(target_range) => {
+                    buffer.range_isqrt_bits(target_range)?;
+                },
+                LeftOperandSource::NamedAccessSource(_) => {},
+            }
+        }
+    }
+    Ok((Historical, None))
+}
+
+/// Integer logarithm, the base is 2 by default: `ilog2`, `ilog10` and `ilog 16` are the same operator.
+pub fn operator_ilog(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    match right {
+        RightOperandSource::DirectSource(base) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    buffer.range_root_bits(target_range, bits);
+                    let bits = buffer.operand_for_range(target_range, base)?;
+                    buffer.range_ilog_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -281,7 +381,7 @@ pub fn operator_root(buffer: &mut BincBuffer, left: LeftOperandSource, right: Ri
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
                     let bits = buffer.get_bits(source_range);
-                    buffer.range_root_bits(target_range, bits);
+                    buffer.range_ilog_bits(target_range, bits)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -290,7 +390,7 @@ pub fn operator_root(buffer: &mut BincBuffer, left: LeftOperandSource, right: Ri
         RightOperandSource::Empty => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    buffer.range_root_bits(target_range, 2);
+                    buffer.range_ilog_bits(target_range, BufferType::from(2u32))?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -386,8 +486,9 @@ pub fn operator_not(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = !buffer.get_bits(source_range);
+                    let bits = buffer.get_bits(source_range);
                     buffer.set_bits(target_range, bits);
+                    buffer.range_not_bits(target_range);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -396,8 +497,7 @@ pub fn operator_not(buffer: &mut BincBuffer, left: LeftOperandSource, right: Rig
         RightOperandSource::Empty => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits = !buffer.get_bits(target_range);
-                    buffer.set_bits(target_range, bits);
+                    buffer.range_not_bits(target_range);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -423,6 +523,220 @@ pub fn operator_reverse(buffer: &mut BincBuffer, left: LeftOperandSource, right:
     Ok((Historical, None))
 }
 
+pub fn operator_byte_swap(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    swap_units(buffer, left, right, 8)
+}
+
+pub fn operator_half_word_swap(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    swap_units(buffer, left, right, 16)
+}
+
+pub fn operator_word_swap(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    swap_units(buffer, left, right, 32)
+}
+
+fn swap_units(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource, unit_bits: usize) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_swap_units(target_range, unit_bits)?;
+        Ok((Historical, None))
+    })
+}
+
+/// Calls the operation upon the target range, the operator takes no second operand.
+fn unary_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource,
+                  operation: impl FnOnce(&mut BincBuffer, BitsIndexRange) -> OperationResult) -> OperationResult {
+    match right {
+        RightOperandSource::Empty => match left {
+            LeftOperandSource::RangeSource(target_range) => operation(buffer, target_range),
+            LeftOperandSource::NamedAccessSource(_) => Ok((Historical, None)),
+        },
+        _ => Err("No second operand allowed!".to_owned())
+    }
+}
+
+/// Calls the operation upon the target range with bits of the second operand as they are, numbers are not converted.
+fn bits_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource,
+                 operation: impl FnOnce(&mut BincBuffer, BitsIndexRange, BufferType)) -> OperationResult {
+    let bits = match right {
+        RightOperandSource::DirectSource(mut second_operand) => {
+            second_operand.signed_extend_to(buffer.max_size());
+            second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit))
+        }
+        RightOperandSource::RangeSource(source_range) => buffer.get_bits(source_range),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Historical, None)),
+        RightOperandSource::Empty => return Err("No second operand!".to_owned())
+    };
+    if let LeftOperandSource::RangeSource(target_range) = left {
+        operation(buffer, target_range, bits);
+    }
+    Ok((Historical, None))
+}
+
+/// Calls the operation upon the target range with the second operand converted for the range, as `+` does.
+fn arithmetic_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource,
+                       operation: impl FnOnce(&mut BincBuffer, BitsIndexRange, BufferType) -> Result<(), String>) -> OperationResult {
+    if let LeftOperandSource::RangeSource(target_range) = left {
+        let bits = match right {
+            RightOperandSource::DirectSource(second_operand) => buffer.operand_for_range(target_range, second_operand)?,
+            RightOperandSource::RangeSource(source_range) => buffer.range_operand_for_range(target_range, source_range),
+            RightOperandSource::NamedAccessSource(_) => return Ok((Historical, None)),
+            RightOperandSource::Empty => return Err("No second operand!".to_owned())
+        };
+        operation(buffer, target_range, bits)?;
+    }
+    Ok((Historical, None))
+}
+
+/// Calls the operation upon the target range with the count of bits to shift, it is 1 if the operand is omitted.
+fn shift_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource,
+                  operation: impl FnOnce(&mut BincBuffer, BitsIndexRange, usize)) -> OperationResult {
+    let count = match right {
+        RightOperandSource::DirectSource(second_operand) => second_operand.to_usize(),
+        RightOperandSource::RangeSource(source_range) => buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Historical, None)),
+        RightOperandSource::Empty => 1
+    };
+    if let LeftOperandSource::RangeSource(target_range) = left {
+        operation(buffer, target_range, count);
+    }
+    Ok((Historical, None))
+}
+
+pub fn operator_add_with_carry(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let carry = buffer.flags().carry;
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_add_with_carry(target_range, bits, carry))
+}
+
+pub fn operator_subtract_with_borrow(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let borrow = buffer.flags().carry;
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_subtract_with_borrow(target_range, bits, borrow))
+}
+
+/// Writes the double width product, or prints its highest half, if there are no bits for it above the range.
+pub fn operator_multiply_wide(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let mut message = None;
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| {
+        if let Some(high_half) = buffer.range_multiply_wide(target_range, bits)? {
+            message = Some(format!("high 0x{:x}", high_half));
+        }
+        Ok(())
+    })?;
+    Ok((Historical, message))
+}
+
+/// Writes the carry-less product like `mulw` does.
+pub fn operator_carryless_multiply(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let mut message = None;
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| {
+        if let Some(high_half) = buffer.range_carryless_multiply(target_range, bits)? {
+            message = Some(format!("high 0x{:x}", high_half));
+        }
+        Ok(())
+    })?;
+    Ok((Historical, message))
+}
+
+/// Sets the quotient to the range and prints the quotient and the remainder.
+pub fn operator_divmod(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let mut message = None;
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| {
+        if buffer.is_zero_for_range(target_range, &bits) {
+            return Err("Cannot divide by 0".to_owned());
+        }
+        let (quotient, remainder) = buffer.range_divmod(target_range, bits)?;
+        message = Some(format!("quotient {} remainder {}", quotient, remainder));
+        Ok(())
+    })?;
+    Ok((Historical, message))
+}
+
+pub fn operator_gcd(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_gcd(target_range, bits))
+}
+
+pub fn operator_lcm(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_lcm(target_range, bits))
+}
+
+/// Modular inverse by the operand, or by the modulus of `modulus` command, if the operand is omitted.
+pub fn operator_modinv(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    match (left, right) {
+        (LeftOperandSource::RangeSource(target_range), RightOperandSource::Empty) => {
+            buffer.range_modinv(target_range, None)?;
+            Ok((Historical, None))
+        }
+        (left, right) => arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_modinv(target_range, Some(bits)))
+    }
+}
+
+pub fn operator_modpow(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    arithmetic_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_modpow(target_range, bits))
+}
+
+pub fn operator_rotate_left_through_carry(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    shift_operator(buffer, left, right, |buffer, target_range, count| buffer.rotate_left_through_carry(target_range, count))
+}
+
+pub fn operator_rotate_right_through_carry(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    shift_operator(buffer, left, right, |buffer, target_range, count| buffer.rotate_right_through_carry(target_range, count))
+}
+
+pub fn operator_isolate_lowest_set_bit(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_isolate_lowest_set_bit(target_range);
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_reset_lowest_set_bit(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_reset_lowest_set_bit(target_range);
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_leading_zeros(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| Ok((Nonhistorical, Some(buffer.range_leading_zeros(target_range).to_string()))))
+}
+
+pub fn operator_trailing_zeros(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| Ok((Nonhistorical, Some(buffer.range_trailing_zeros(target_range).to_string()))))
+}
+
+pub fn operator_deposit_bits(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    bits_operator(buffer, left, right, |buffer, target_range, mask| buffer.range_deposit_bits(target_range, mask))
+}
+
+pub fn operator_extract_bits(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    bits_operator(buffer, left, right, |buffer, target_range, mask| buffer.range_extract_bits(target_range, mask))
+}
+
+pub fn operator_bitfield_extract(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    bits_operator(buffer, left, right, |buffer, target_range, control| buffer.range_bitfield_extract(target_range, control))
+}
+
+pub fn operator_bitfield_insert(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    bits_operator(buffer, left, right, |buffer, target_range, bits| buffer.range_bitfield_insert(target_range, bits))
+}
+
+/// Prints bytes of the range in the order they lie in memory of big-endian and little-endian machines.
+pub fn operator_memory_order(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    if let RightOperandSource::Empty = right {} else {
+        return Err("No second operand allowed!".to_owned());
+    }
+    match left {
+        LeftOperandSource::RangeSource(target_range) => {
+            let little_endian = buffer.range_bytes_le(target_range);
+            let to_hex = |bytes: &mut dyn Iterator<Item = &u8>| bytes.map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" ");
+            Ok((Nonhistorical, Some(format!(
+                "big endian: {}  little endian: {}",
+                to_hex(&mut little_endian.iter().rev()), to_hex(&mut little_endian.iter())
+            ))))
+        }
+        LeftOperandSource::NamedAccessSource(_) => Ok((Nonhistorical, None)),
+    }
+}
+
 pub fn operator_random(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
         RightOperandSource::DirectSource(_) => return Err("No second operand allowed!".to_owned()),
@@ -431,7 +745,7 @@ pub fn operator_random(buffer: &mut BincBuffer, left: LeftOperandSource, right:
         RightOperandSource::Empty => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    buffer.set_bits(target_range, random::<u128>());
+                    buffer.range_random_bits(target_range);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -440,6 +754,73 @@ pub fn operator_random(buffer: &mut BincBuffer, left: LeftOperandSource, right:
     Ok((Historical, None))
 }
 
+pub fn operator_to_bcd(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_to_bcd(target_range)?;
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_from_bcd(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_from_bcd(target_range)?;
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_to_gray(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_to_gray(target_range);
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_from_gray(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_from_gray(target_range);
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_to_zigzag(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_to_zigzag(target_range);
+        Ok((Historical, None))
+    })
+}
+
+pub fn operator_from_zigzag(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        buffer.range_from_zigzag(target_range);
+        Ok((Historical, None))
+    })
+}
+
+/// Encodes or decodes a LEB128 varint and prints its bytes, as they go in memory.
+fn leb128_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource, signed: bool, encode: bool) -> OperationResult {
+    unary_operator(buffer, left, right, |buffer, target_range| {
+        let bytes = if encode { buffer.range_to_leb128(target_range, signed)? } else { buffer.range_from_leb128(target_range, signed)? };
+        let bytes = bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" ");
+        Ok((Historical, Some(format!("bytes {}", bytes))))
+    })
+}
+
+pub fn operator_to_leb128(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    leb128_operator(buffer, left, right, false, true)
+}
+
+pub fn operator_from_leb128(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    leb128_operator(buffer, left, right, false, false)
+}
+
+pub fn operator_to_sleb128(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    leb128_operator(buffer, left, right, true, true)
+}
+
+pub fn operator_from_sleb128(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    leb128_operator(buffer, left, right, true, false)
+}
+
 pub fn operator_shuffle(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
         RightOperandSource::DirectSource(_) => return Err("No second operand allowed!".to_owned()),
@@ -463,7 +844,7 @@ pub fn operator_signed_shift_left(buffer: &mut BincBuffer, left: LeftOperandSour
         RightOperandSource::DirectSource(second_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    buffer.signed_shift_left(target_range, second_operand.to_usize());
+                    buffer.signed_shift_left(target_range, second_operand.to_usize())?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -471,8 +852,8 @@ pub fn operator_signed_shift_left(buffer: &mut BincBuffer, left: LeftOperandSour
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let count = buffer.get_bits(source_range) as usize;
-                    buffer.signed_shift_left(target_range, count);
+                    let count = buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX);
+                    buffer.signed_shift_left(target_range, count)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -481,7 +862,7 @@ pub fn operator_signed_shift_left(buffer: &mut BincBuffer, left: LeftOperandSour
         RightOperandSource::Empty => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    buffer.signed_shift_left(target_range, 1);
+                    buffer.signed_shift_left(target_range, 1)?;
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -497,7 +878,7 @@ pub fn operator_signed_shift_right(buffer: &mut BincBuffer, left: LeftOperandSou
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
                     trace!("operator_signed_shift_right: {:?} {}", target_range, second_operand.to_usize());
-                    buffer.signed_shift_right(target_range, second_operand.to_usize());
+                    buffer.shift_right(target_range, second_operand.to_usize());
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -505,9 +886,9 @@ pub fn operator_signed_shift_right(buffer: &mut BincBuffer, left: LeftOperandSou
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let count = buffer.get_bits(source_range) as usize;
+                    let count = buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX);
                     trace!("operator_signed_shift_right: {:?} {:?}, count {}", target_range, source_range, count);
-                    buffer.signed_shift_right(target_range, count);
+                    buffer.shift_right(target_range, count);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -517,7 +898,7 @@ pub fn operator_signed_shift_right(buffer: &mut BincBuffer, left: LeftOperandSou
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
                     trace!("operator_signed_shift_right: {:?} shit one", target_range);
-                    buffer.signed_shift_right(target_range, 1);
+                    buffer.shift_right(target_range, 1);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -539,7 +920,7 @@ pub fn operator_unsigned_shift_right(buffer: &mut BincBuffer, left: LeftOperandS
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let count = buffer.get_bits(source_range) as usize;
+                    let count = buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX);
                     buffer.unsigned_shift_right(target_range, count);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
@@ -571,7 +952,7 @@ pub fn operator_unsigned_cyclic_shift_right(buffer: &mut BincBuffer, left: LeftO
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let count = buffer.get_bits(source_range) as usize;
+                    let count = buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX);
                     buffer.unsigned_cyclic_shift_right(target_range, count);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
@@ -603,7 +984,7 @@ pub fn operator_unsigned_cyclic_shift_left(buffer: &mut BincBuffer, left: LeftOp
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let count = buffer.get_bits(source_range) as usize;
+                    let count = buffer.get_bits(source_range).to_usize().unwrap_or(usize::MAX);
                     buffer.unsigned_cyclic_shift_left(target_range, count);
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
@@ -625,10 +1006,40 @@ pub fn operator_unsigned_cyclic_shift_left(buffer: &mut BincBuffer, left: LeftOp
 pub fn operator_int_bits_width(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
         RightOperandSource::DirectSource(number) => {
-            buffer.convert(BincBufferType::Integer, buffer.signed(), number.to_usize());
+            buffer.convert(BincBufferType::Integer, buffer.signed(), number.to_usize())?;
+            Ok((Historical, None))
+        }
+        _ => Err("Bit width is a necessary argument".to_owned())
+    }
+}
+
+pub fn operator_float_bits_width(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    match right {
+        RightOperandSource::DirectSource(number) => {
+            let format = FloatFormat::from_width(number.to_usize())?;
+            buffer.convert(BincBufferType::Float(format), true, format.width())?;
             Ok((Historical, None))
         }
-        _ => return Err("Bit width is a necessary argument".to_owned())
+        _ => Err("Bit width is a necessary argument".to_owned())
+    }
+}
+
+/// The operand is an empty number of a floating point or a fixed point format,
+/// the buffer takes its format, the bits do not change.
+pub fn operator_number_format(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    match right {
+        RightOperandSource::DirectSource(number) => match number.buffer_type() {
+            BincBufferType::Float(format) => {
+                buffer.convert(BincBufferType::Float(format), true, format.width())?;
+                Ok((Historical, None))
+            }
+            BincBufferType::Fixed(format) => {
+                buffer.convert(BincBufferType::Fixed(format), format.signed, format.width())?;
+                Ok((Historical, None))
+            }
+            _ => Err("Number format is a necessary argument".to_owned())
+        }
+        _ => Err("Number format is a necessary argument".to_owned())
     }
 }
 
@@ -636,14 +1047,14 @@ pub fn operator_count(buffer: &mut BincBuffer, left: LeftOperandSource, right: R
     match right {
         RightOperandSource::DirectSource(second_operand) => match left {
             LeftOperandSource::RangeSource(target_range) => {
-                let count = match second_operand.to_u128() {
+                let count = match second_operand.to_usize() {
                     0 => buffer.range_count_bits(target_range, 0),
                     1 => buffer.range_count_bits(target_range, 1),
                     _ => return Err("Counting only 1 and 0".to_owned())
                 };
                 return Ok((Nonhistorical, Some(count.to_string())));
             }
-            _ => todo!("not yet implemented for float and fixed")
+            LeftOperandSource::NamedAccessSource(_) => return Err("Count operation counts bits of a range, like [7:0]cnt 1".to_owned())
         },
         RightOperandSource::RangeSource(_) => {
             return Err("Count operation does not read range, specify 1 or 0".to_owned());
@@ -662,79 +1073,316 @@ pub fn operator_count(buffer: &mut BincBuffer, left: LeftOperandSource, right: R
 }
 
 pub fn operator_signed(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
-    buffer.convert(BincBufferType::Integer, true, buffer.max_size());
+    buffer.convert(BincBufferType::Integer, true, buffer.max_size())?;
+    buffer.set_representation(Representation::TwosComplement)?;
     Ok((Historical, None))
 }
 
 pub fn operator_unsigned(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
-    buffer.convert(BincBufferType::Integer, false, buffer.max_size());
+    buffer.convert(BincBufferType::Integer, false, buffer.max_size())?;
+    buffer.set_representation(Representation::TwosComplement)?;
     Ok((Historical, None))
 }
 
-pub fn operator_greater(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+pub fn operator_ones_complement(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_representation(Representation::OnesComplement)?;
+    Ok((Historical, None))
+}
+
+pub fn operator_sign_magnitude(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_representation(Representation::SignMagnitude)?;
+    Ok((Historical, None))
+}
+
+/// Offset binary with the given excess, or with 2^(n-1), which follows the width of the buffer.
+pub fn operator_excess(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let excess = match right {
+        RightOperandSource::DirectSource(excess) if excess.is_negative() => return Err("The excess must not be negative".to_owned()),
+        RightOperandSource::DirectSource(excess) => Some(excess.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit))),
+        RightOperandSource::RangeSource(source_range) => Some(buffer.get_bits(source_range)),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+        RightOperandSource::Empty => None
+    };
+    buffer.set_representation(Representation::Excess(excess))?;
+    Ok((Historical, None))
+}
+
+pub fn operator_negabinary(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_representation(Representation::Negabinary)?;
+    Ok((Historical, None))
+}
+
+pub fn operator_code_point(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_text_encoding(TextEncoding::CodePoint)?;
+    Ok((Historical, None))
+}
+
+pub fn operator_utf8(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_text_encoding(TextEncoding::Utf8)?;
+    Ok((Historical, None))
+}
+
+pub fn operator_utf16le(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_text_encoding(TextEncoding::Utf16Le)?;
+    Ok((Historical, None))
+}
+
+pub fn operator_utf16be(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_text_encoding(TextEncoding::Utf16Be)?;
+    Ok((Historical, None))
+}
+
+/// Names the range of the operand, `let opcode` with no range forgets the name.
+pub fn operator_let(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let name = match left {
+        LeftOperandSource::NamedAccessSource(NamedAccess::NewRange(name)) => name,
+        _ => return Err("`let` names a range, like `let opcode = [31:26]`".to_owned())
+    };
+    let range = match right {
+        RightOperandSource::RangeSource(range) => Some(range),
+        RightOperandSource::Empty => None,
+        _ => return Err(format!("{} can be a name of a range only, like `let {} = [31:26]`", name, name))
+    };
+    buffer.set_named_range(name, range)?;
+    Ok((Historical, None))
+}
+
+/// Prints the named ranges, from the first one named.
+pub fn operator_ranges(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    let ranges = buffer.named_ranges().iter()
+        .map(|(name, range)| format!("{} {}", name, buffer.range_to_string(*range)))
+        .collect::<Vec<_>>();
+    let message = if ranges.is_empty() { "no named ranges".to_owned() } else { ranges.join("  ") };
+    Ok((Nonhistorical, Some(message)))
+}
+
+pub fn operator_truncated_division(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_division(Division::Truncated);
+    Ok((Historical, None))
+}
+
+pub fn operator_floored_division(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_division(Division::Floored);
+    Ok((Historical, None))
+}
+
+pub fn operator_wrap(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_overflow_mode(OverflowMode::Wrap);
+    Ok((Historical, None))
+}
+
+pub fn operator_saturate(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_overflow_mode(OverflowMode::Saturate);
+    Ok((Historical, None))
+}
+
+pub fn operator_checked(buffer: &mut BincBuffer, _: LeftOperandSource, _: RightOperandSource) -> OperationResult {
+    buffer.set_overflow_mode(OverflowMode::Checked);
+    Ok((Historical, None))
+}
+
+/// Turns GF(2^n) arithmetics on with the reduction polynomial, `gf 0x11b` is the field of AES, or turns it off.
+pub fn operator_field(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let polynomial = match right {
+        RightOperandSource::DirectSource(polynomial) if polynomial.is_negative() => return Err("The reduction polynomial must be a positive number".to_owned()),
+        RightOperandSource::DirectSource(polynomial) => Some(polynomial.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit))),
+        RightOperandSource::RangeSource(source_range) => Some(buffer.get_bits(source_range)),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+        RightOperandSource::Empty => None
+    };
+    buffer.set_field(polynomial)?;
+    Ok((Historical, None))
+}
+
+/// Sets the CRC model, which the syntax has put into the carrier, and prints its parameters.
+pub fn operator_crc_model(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
-        RightOperandSource::DirectSource(second_operand) => {
-            match left {
-                LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op > bits_second_op { "yes" } else { "no" }).to_owned())));
-                },
-                LeftOperandSource::NamedAccessSource(_) => {},
-            }
+        RightOperandSource::DirectSource(model_carrier) => {
+            buffer.set_crc_model(model_carrier.crc_model());
+            Ok((Historical, Some(model_carrier.crc_model().describe())))
+        }
+        _ => Err("CRC model is a necessary argument".to_owned())
+    }
+}
+
+/// Changes a parameter of the current CRC model, which becomes a custom one, and prints the parameters.
+fn crc_parameter(buffer: &mut BincBuffer, right: RightOperandSource, set: fn(&mut CrcModel, u64) -> Result<(), String>) -> OperationResult {
+    let value = match right {
+        RightOperandSource::DirectSource(number) if number.is_negative() => return Err("CRC parameters are positive numbers".to_owned()),
+        RightOperandSource::DirectSource(number) => number.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)),
+        RightOperandSource::RangeSource(source_range) => buffer.get_bits(source_range),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+        RightOperandSource::Empty => return Err("No second operand!".to_owned())
+    };
+    let mut model = buffer.crc_model();
+    set(&mut model, value.to_u64().unwrap_or(u64::MAX))?;
+    model.name = "custom";
+    buffer.set_crc_model(model);
+    Ok((Historical, Some(model.describe())))
+}
+
+fn fits_crc(model: &CrcModel, value: u64) -> Result<u64, String> {
+    if model.width < 64 && value >> model.width != 0 {
+        return Err(format!("The value does not fit CRC-{}", model.width));
+    }
+    Ok(value)
+}
+
+fn crc_flag(value: u64) -> Result<bool, String> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err("Reflection is on with 1 and off with 0".to_owned())
+    }
+}
+
+pub fn operator_crc_init(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    crc_parameter(buffer, right, |model, value| { model.init = fits_crc(model, value)?; Ok(()) })
+}
+
+pub fn operator_crc_xorout(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    crc_parameter(buffer, right, |model, value| { model.xorout = fits_crc(model, value)?; Ok(()) })
+}
+
+pub fn operator_crc_refin(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    crc_parameter(buffer, right, |model, value| { model.refin = crc_flag(value)?; Ok(()) })
+}
+
+pub fn operator_crc_refout(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    crc_parameter(buffer, right, |model, value| { model.refout = crc_flag(value)?; Ok(()) })
+}
+
+/// Checksum of the bytes of the target range is printed, checksum of the bytes of the source range is written to the target range.
+fn checksum_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource,
+                     checksum: impl FnOnce(&BincBuffer, &[u8]) -> (String, u64, usize)) -> OperationResult {
+    let target_range = match left {
+        LeftOperandSource::RangeSource(target_range) => target_range,
+        LeftOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+    };
+    match right {
+        RightOperandSource::Empty => {
+            let (name, value, bits) = checksum(buffer, &buffer.range_bytes_be(target_range)?);
+            Ok((Nonhistorical, Some(format!("{} 0x{:02$x}", name, value, bits.div_ceil(4)))))
         }
         RightOperandSource::RangeSource(source_range) => {
-            match left {
-                LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = buffer.get_bits(source_range);
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op > bits_second_op { "yes" } else { "no" }).to_owned())));
-                },
-                LeftOperandSource::NamedAccessSource(_) => {},
-            }
+            let (_, value, bits) = checksum(buffer, &buffer.range_bytes_be(source_range)?);
+            buffer.range_set_checksum(target_range, value, bits)?;
+            Ok((Historical, None))
         }
-        RightOperandSource::NamedAccessSource(_) => {},
-        RightOperandSource::Empty => return Err("No second operand!".to_owned())
+        RightOperandSource::NamedAccessSource(_) => Ok((Nonhistorical, None)),
+        RightOperandSource::DirectSource(_) => Err("Checksum is computed over a range: [71:0]crc prints it, [31:0]crc [103:32] writes it".to_owned())
     }
-    Ok((Historical, None))
 }
 
-pub fn operator_less(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+/// Prints the parity bit of the target range, or writes the parity bit of the source range to the target range.
+fn parity_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource, odd: bool) -> OperationResult {
+    let target_range = match left {
+        LeftOperandSource::RangeSource(target_range) => target_range,
+        LeftOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+    };
     match right {
-        RightOperandSource::DirectSource(second_operand) => {
-            match left {
-                LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op < bits_second_op { "yes" } else { "no" }).to_owned())));
-                },
-                LeftOperandSource::NamedAccessSource(_) => {},
-            }
+        RightOperandSource::Empty => {
+            let (ones, parity) = buffer.range_parity(target_range, odd);
+            Ok((Nonhistorical, Some(format!("ones {}  {} parity {}", ones, if odd { "odd" } else { "even" }, parity as u8))))
         }
         RightOperandSource::RangeSource(source_range) => {
-            match left {
-                LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = buffer.get_bits(source_range);
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op < bits_second_op { "yes" } else { "no" }).to_owned())));
-                },
-                LeftOperandSource::NamedAccessSource(_) => {},
-            }
+            let (_, parity) = buffer.range_parity(source_range, odd);
+            buffer.range_set_checksum(target_range, parity as u64, 1)?;
+            Ok((Historical, None))
         }
-        RightOperandSource::NamedAccessSource(_) => {},
-        RightOperandSource::Empty => return Err("No second operand!".to_owned())
+        RightOperandSource::NamedAccessSource(_) => Ok((Nonhistorical, None)),
+        RightOperandSource::DirectSource(_) => Err("Parity is computed over a range: [7:0]parity prints it, [8]parity [7:0] writes it".to_owned())
     }
+}
+
+pub fn operator_parity(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    parity_operator(buffer, left, right, false)
+}
+
+pub fn operator_odd_parity(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    parity_operator(buffer, left, right, true)
+}
+
+/// Encodes the data of the source range to the target range, or decodes the codeword of the source range and prints
+/// the syndrome and the corrected bit.
+fn hamming_operator(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource, secded: bool, encode: bool) -> OperationResult {
+    let (target_range, source_range) = match (left, right) {
+        (LeftOperandSource::RangeSource(target_range), RightOperandSource::RangeSource(source_range)) => (target_range, source_range),
+        (_, RightOperandSource::NamedAccessSource(_)) | (LeftOperandSource::NamedAccessSource(_), _) => return Ok((Nonhistorical, None)),
+        _ => return Err("The data and the codeword are ranges: [6:0]hamenc [3:0] encodes, [3:0]hamdec [6:0] decodes".to_owned())
+    };
+    if encode {
+        buffer.range_hamming_encode(target_range, source_range, secded)?;
+        return Ok((Historical, None));
+    }
+    let decoded = buffer.range_hamming_decode(target_range, source_range, secded)?;
+    let message = match decoded.corrected_bit {
+        Some(bit) => format!("syndrome 0x{:x}  corrected bit {}", decoded.syndrome, bit),
+        None => format!("syndrome 0x{:x}  no error", decoded.syndrome),
+    };
+    Ok((Historical, Some(message)))
+}
+
+pub fn operator_hamming_encode(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    hamming_operator(buffer, left, right, false, true)
+}
+
+pub fn operator_hamming_decode(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    hamming_operator(buffer, left, right, false, false)
+}
+
+pub fn operator_secded_encode(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    hamming_operator(buffer, left, right, true, true)
+}
+
+pub fn operator_secded_decode(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    hamming_operator(buffer, left, right, true, false)
+}
+
+/// CRC of the current model, which `crc32`, `crc16ccitt` or `crc16 0x1021` sets.
+pub fn operator_crc(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    checksum_operator(buffer, left, right, |buffer, bytes| {
+        let model = buffer.crc_model();
+        (model.title(), model.checksum(bytes), model.width)
+    })
+}
+
+pub fn operator_internet_checksum(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    checksum_operator(buffer, left, right, |_, bytes| ("Internet checksum".to_owned(), internet_checksum(bytes) as u64, 16))
+}
+
+pub fn operator_adler32(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    checksum_operator(buffer, left, right, |_, bytes| ("Adler-32".to_owned(), adler32(bytes) as u64, 32))
+}
+
+/// Sets the modulus of `modpow` and `modinv`, it is as wide as the number, or prints it, if the number is omitted.
+pub fn operator_modulus(buffer: &mut BincBuffer, _: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    let modulus = match right {
+        RightOperandSource::DirectSource(modulus) if modulus.is_negative() => return Err("The modulus must be a positive number".to_owned()),
+        RightOperandSource::DirectSource(modulus) => modulus.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)),
+        RightOperandSource::RangeSource(source_range) => buffer.get_bits(source_range),
+        RightOperandSource::NamedAccessSource(_) => return Ok((Nonhistorical, None)),
+        RightOperandSource::Empty => {
+            let message = match buffer.modulus() {
+                Some(modulus) => format!("modulus {}", modulus),
+                None => "no modulus".to_owned()
+            };
+            return Ok((Nonhistorical, Some(message)));
+        }
+    };
+    buffer.set_modulus(modulus)?;
     Ok((Historical, None))
 }
 
-pub fn operator_equals(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+/// Compares the target range with the operand by value, the buffer does not change.
+fn compare(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource, is_true: fn(Option<Ordering>) -> bool) -> OperationResult {
     match right {
         RightOperandSource::DirectSource(second_operand) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = second_operand.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op == bits_second_op { "yes" } else { "no" }).to_owned())));
+                    let bits_second_op = buffer.operand_for_range(target_range, second_operand)?;
+                    let ordering = buffer.range_compare(target_range, &bits_second_op);
+                    return Ok((Nonhistorical, Some((if is_true(ordering) { "yes" } else { "no" }).to_owned())));
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -742,9 +1390,9 @@ pub fn operator_equals(buffer: &mut BincBuffer, left: LeftOperandSource, right:
         RightOperandSource::RangeSource(source_range) => {
             match left {
                 LeftOperandSource::RangeSource(target_range) => {
-                    let bits_second_op = buffer.get_bits(source_range);
-                    let bits_first_op = buffer.get_bits(target_range);
-                    return Ok((Nonhistorical, Some((if bits_first_op == bits_second_op { "yes" } else { "no" }).to_owned())));
+                    let bits_second_op = buffer.range_operand_for_range(target_range, source_range);
+                    let ordering = buffer.range_compare(target_range, &bits_second_op);
+                    return Ok((Nonhistorical, Some((if is_true(ordering) { "yes" } else { "no" }).to_owned())));
                 },
                 LeftOperandSource::NamedAccessSource(_) => {},
             }
@@ -755,6 +1403,31 @@ pub fn operator_equals(buffer: &mut BincBuffer, left: LeftOperandSource, right:
     Ok((Historical, None))
 }
 
+pub fn operator_greater(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| ordering == Some(Ordering::Greater))
+}
+
+pub fn operator_greater_or_equal(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| matches!(ordering, Some(Ordering::Greater) | Some(Ordering::Equal)))
+}
+
+pub fn operator_less(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| ordering == Some(Ordering::Less))
+}
+
+pub fn operator_less_or_equal(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| matches!(ordering, Some(Ordering::Less) | Some(Ordering::Equal)))
+}
+
+pub fn operator_equals(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| ordering == Some(Ordering::Equal))
+}
+
+/// NaN is not equal to anything.
+pub fn operator_not_equals(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
+    compare(buffer, left, right, |ordering| ordering != Some(Ordering::Equal))
+}
+
 pub fn operator_swap(buffer: &mut BincBuffer, left: LeftOperandSource, right: RightOperandSource) -> OperationResult {
     match right {
         RightOperandSource::DirectSource(_) => return Err("Cannot swap with rvalue!".to_owned()),
@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::buffer::{BincBuffer, BitsIndexRange, BitIndex};
+use crate::buffer::{BincBuffer, BitsIndexRange, BitIndex, BincBufferType};
+use crate::float::{FloatFormat, FloatLiteral};
+use crate::fixed::{FixedFormat, Rounding};
+use crate::checksum::CrcModel;
+use num_traits::ToPrimitive;
+use crate::buffer::BufferType;
 use crate::operators::Operator;
 use crate::operators::operator_show_help;
 use crate::operators::operator_assign;
@@ -38,9 +43,78 @@ use crate::operators::operator_unsigned_shift_right;
 use crate::operators::operator_unsigned_cyclic_shift_right;
 use crate::operators::operator_unsigned_cyclic_shift_left;
 use crate::operators::operator_int_bits_width;
+use crate::operators::operator_float_bits_width;
+use crate::operators::operator_number_format;
 use crate::operators::operator_count;
 use crate::operators::operator_signed;
 use crate::operators::operator_unsigned;
+use crate::operators::operator_truncated_division;
+use crate::operators::operator_floored_division;
+use crate::operators::operator_wrap;
+use crate::operators::operator_isqrt;
+use crate::operators::operator_byte_swap;
+use crate::operators::operator_half_word_swap;
+use crate::operators::operator_word_swap;
+use crate::operators::operator_memory_order;
+use crate::operators::operator_isolate_lowest_set_bit;
+use crate::operators::operator_reset_lowest_set_bit;
+use crate::operators::operator_leading_zeros;
+use crate::operators::operator_trailing_zeros;
+use crate::operators::operator_deposit_bits;
+use crate::operators::operator_extract_bits;
+use crate::operators::operator_bitfield_extract;
+use crate::operators::operator_bitfield_insert;
+use crate::operators::operator_add_with_carry;
+use crate::operators::operator_multiply_wide;
+use crate::operators::operator_divmod;
+use crate::operators::operator_gcd;
+use crate::operators::operator_lcm;
+use crate::operators::operator_modinv;
+use crate::operators::operator_modpow;
+use crate::operators::operator_modulus;
+use crate::operators::operator_carryless_multiply;
+use crate::operators::operator_field;
+use crate::operators::operator_crc;
+use crate::operators::operator_crc_model;
+use crate::operators::operator_crc_init;
+use crate::operators::operator_crc_xorout;
+use crate::operators::operator_crc_refin;
+use crate::operators::operator_crc_refout;
+use crate::operators::operator_internet_checksum;
+use crate::operators::operator_adler32;
+use crate::operators::operator_parity;
+use crate::operators::operator_odd_parity;
+use crate::operators::operator_hamming_encode;
+use crate::operators::operator_hamming_decode;
+use crate::operators::operator_secded_encode;
+use crate::operators::operator_secded_decode;
+use crate::operators::operator_ones_complement;
+use crate::operators::operator_sign_magnitude;
+use crate::operators::operator_excess;
+use crate::operators::operator_negabinary;
+use crate::operators::operator_code_point;
+use crate::operators::operator_utf8;
+use crate::operators::operator_utf16le;
+use crate::operators::operator_utf16be;
+use crate::operators::operator_to_bcd;
+use crate::operators::operator_from_bcd;
+use crate::operators::operator_to_gray;
+use crate::operators::operator_from_gray;
+use crate::operators::operator_to_zigzag;
+use crate::operators::operator_from_zigzag;
+use crate::operators::operator_to_leb128;
+use crate::operators::operator_from_leb128;
+use crate::operators::operator_to_sleb128;
+use crate::operators::operator_from_sleb128;
+use crate::operators::operator_subtract_with_borrow;
+use crate::operators::operator_rotate_left_through_carry;
+use crate::operators::operator_rotate_right_through_carry;
+use crate::operators::operator_ilog;
+use crate::operators::operator_saturate;
+use crate::operators::operator_checked;
+use crate::operators::operator_not_equals;
+use crate::operators::operator_greater_or_equal;
+use crate::operators::operator_less_or_equal;
 use crate::operators::operator_greater;
 use crate::operators::operator_less;
 use crate::operators::operator_equals;
@@ -48,14 +122,24 @@ use crate::operators::operator_swap;
 use crate::operators::operator_negate;
 use crate::operators::operator_undo;
 use crate::operators::operator_redo;
+use crate::operators::operator_let;
+use crate::operators::operator_ranges;
 use log::trace;
 use std::iter::FromIterator;
 
+/// Named parts of the buffer, they are resolved to ranges by `resolve_named_access` before an operator is called,
+/// carry flag is resolved to its value.
 #[derive(Debug)]
 pub enum NamedAccess {
+    Sign,
     Exponent,
     Fraction,
     Carry,
+    /// a range, which has been named by `let`
+    Range(String),
+    /// the name, which `let` gives to a range, it is not resolved
+    NewRange(String),
+    #[allow(dead_code)]
     None,
 }
 
@@ -76,6 +160,8 @@ pub enum RightOperandSource {
 pub struct ParsingIterator {
     source: Vec<char>,
     offset: usize,
+    /// floating point literals are encoded to the format of the buffer, or truncated to integers
+    number_type: BincBufferType,
 }
 
 impl ParsingIterator {
@@ -87,7 +173,12 @@ impl ParsingIterator {
         while offset < source.len() && (source[offset]).is_whitespace() {
             offset += 1;
         }
-        Ok(Self { source, offset })
+        Ok(Self { source, offset, number_type: BincBufferType::Integer })
+    }
+
+    pub fn with_number_type(mut self, number_type: BincBufferType) -> Self {
+        self.number_type = number_type;
+        self
     }
 
     pub fn current(&self) -> Option<char> {
@@ -99,9 +190,8 @@ impl ParsingIterator {
     }
 
     pub fn match_from_current(&self, sequence: &str) -> bool {
-        let bytes: Vec<char> = sequence.chars().collect();
-        for i in 0..sequence.len() {
-            if self.offset + i >= self.source.len() || self.source[self.offset + i] != bytes[i] {
+        for (i, c) in sequence.chars().enumerate() {
+            if self.offset + i >= self.source.len() || self.source[self.offset + i] != c {
                 trace!("match_from_current: no match");
                 return false
             }
@@ -110,6 +200,17 @@ impl ParsingIterator {
         true
     }
 
+    /// The symbol n positions after the current one, whitespaces are not skipped.
+    pub fn peek(&self, n: usize) -> Option<char> {
+        self.source.get(self.offset + n).copied()
+    }
+
+    /// Matches the sequence, if it is not followed by a letter or a digit.
+    pub fn match_word_from_current(&self, word: &str) -> bool {
+        let after_word = self.offset + word.chars().count();
+        self.match_from_current(word) && (after_word >= self.source.len() || !self.source[after_word].is_alphanumeric())
+    }
+
     pub fn next(&mut self) -> Option<char> {
         if self.offset < self.source.len() {
             self.offset += 1;
@@ -146,7 +247,7 @@ pub fn syntax_index(mut it: ParsingIterator) -> (ParsingIterator, Option<usize>)
     let mut acc = 0usize;
     match it.current() {
         Some(c) => {
-            if !('0'..='9').contains(&c) {
+            if !c.is_ascii_digit() {
                 return (it, None);
             }
         }
@@ -188,71 +289,257 @@ fn syntax_range(it: ParsingIterator) -> (ParsingIterator, BitsIndexRange) {
 fn syntax_accessor(it: ParsingIterator) -> Result<(ParsingIterator, Option<BitsIndexRange>), String> {
     trace!("syntax_accessor: {:?}", it.current());
     match it.current() {
-        Some(c) => match c {
-            '[' => {
-                let (current_it, range) = syntax_range(it.rewind_n(1));
-                if let Some(c) = current_it.current() {
-                    if c == ']' {
-                        Ok((current_it.rewind_n(1), Some(range)))
-                    } else {
-                        Err("Accessor [] is not closed with ']'".to_owned())
-                    }
+        Some('[') => {
+            let (current_it, range) = syntax_range(it.rewind_n(1));
+            if let Some(c) = current_it.current() {
+                if c == ']' {
+                    Ok((current_it.rewind_n(1), Some(range)))
                 } else {
-                    Err("Command is not complete".to_owned())
+                    Err("Accessor [] is not closed with ']'".to_owned())
                 }
+            } else {
+                Err("Command is not complete".to_owned())
             }
-            // TODO named ranges
-            _ => Ok((it, None))
         }
-        None => Ok((it, None))
+        _ => Ok((it, None))
     }
 }
 
+fn syntax_named_access(it: ParsingIterator) -> (ParsingIterator, Option<NamedAccess>) {
+    trace!("syntax_named_access: {:?}", it.current());
+    match it.current() {
+        Some('s') if it.match_word_from_current("s") => (it.rewind_n(1), Some(NamedAccess::Sign)),
+        Some('e') if it.match_word_from_current("e") => (it.rewind_n(1), Some(NamedAccess::Exponent)),
+        Some('f') if it.match_word_from_current("f") => (it.rewind_n(1), Some(NamedAccess::Fraction)),
+        Some('c') if it.match_word_from_current("c") => (it.rewind_n(1), Some(NamedAccess::Carry)),
+        _ => (it, None)
+    }
+}
+
+/// A word of letters, digits and underscores, which starts with a letter, and is not a keyword.
+fn syntax_range_name(it: ParsingIterator) -> (ParsingIterator, Option<String>) {
+    match range_name(&it) {
+        Some(name) => {
+            let length = name.chars().count();
+            (it.rewind_n(length), Some(name))
+        }
+        None => (it, None)
+    }
+}
+
+/// The range name at the current symbol; the whole word is compared with keywords,
+/// a keyword may be followed by digits of its operand, like int16, so `interrupt` is a name and `int16` is not.
+fn range_name(it: &ParsingIterator) -> Option<String> {
+    if !it.current().is_some_and(|c| c.is_alphabetic() || c == '_') {
+        return None;
+    }
+    let name = (0..).map_while(|i| it.peek(i)).take_while(|c| c.is_alphanumeric() || *c == '_').collect::<String>();
+    let is_float_name = ["inf", "eps", "nan", "NaN", "sNaN"].contains(&name.as_str());
+    let is_keyword = match ParsingIterator::from(&name).map(syntax_operator) {
+        Ok((after_keyword, Some(_))) => !after_keyword.current().is_some_and(|c| c.is_alphabetic() || c == '_'),
+        Ok((_, None)) => false,
+        Err(_) => true
+    };
+    if is_float_name || is_keyword {
+        return None;
+    }
+    Some(name)
+}
+
 fn syntax_letter(it: ParsingIterator) -> Result<(ParsingIterator, RightOperandSource), String> {
     trace!("syntax_letter: {:?}", it.current());
     match it.current() {
-        Some(c) => match c {
-            '\'' => {
-                // TODO support for emojis, which take more then 1 byte, need to check
-                //  if the char ' is a part of emoji
-                // TODO add support for unicode literals like '\x03BB' or '\u1132'
-                let current_it = it.rewind_n_include_whitespaces(1);
-                if let Some(c) = current_it.current() {
-                    let number = BincBuffer::from_char(c).unwrap();
-                    let current_it = current_it.rewind_n(1);
-                    if let Some(c) = current_it.current() {
-                        if c == '\'' {
-                            Ok((current_it.rewind_n(1), RightOperandSource::DirectSource(number)))
-                        } else {
-                            Err("Letter is not closed with '\\'', only one letter allowed".to_owned())
-                        }
-                    } else {
-                        Err("Command is not complete".to_owned())
-                    }
-                } else {
-                    Err("Letter expected but no characters found".to_owned())
+        // `'''` is the quote itself, as it has been before escapes
+        Some('\'') if it.peek(1) == Some('\'') && it.peek(2) == Some('\'') =>
+            Ok((it.rewind_n_include_whitespaces(2).rewind_n(1), RightOperandSource::DirectSource(BincBuffer::from_char('\'')?))),
+        Some('\'') => {
+            let (it, pieces) = syntax_quoted(it, '\'')?;
+            let c = match pieces[..] {
+                [TextPiece::Char(c)] => c,
+                // a byte escape is the code point of the byte
+                [TextPiece::Byte(byte)] => byte as char,
+                [] => return Err("Letter expected but no characters found".to_owned()),
+                _ => {
+                    let letters = pieces.iter()
+                        .map(|piece| match piece { TextPiece::Char(c) => *c, TextPiece::Byte(byte) => *byte as char })
+                        .collect::<String>();
+                    let code_points = letters.chars().map(|c| format!("U+{:04X}", c as u32)).collect::<Vec<_>>();
+                    return Err(format!(
+                        "'{}' is {} code points {}, a letter literal takes one, a string literal in double quotes takes several",
+                        letters.escape_debug(), code_points.len(), code_points.join(" ")
+                    ));
                 }
-            }
-            _ => Err("Given input is not a letter literal".to_owned())
+            };
+            Ok((it, RightOperandSource::DirectSource(BincBuffer::from_char(c)?)))
         }
+        Some(_) => Err("Given input is not a letter literal".to_owned()),
         None => Err("No letter present in command as right operand".to_owned())
     }
 }
 
+/// A character of a literal, or a byte of a `\x41` escape.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum TextPiece {
+    Char(char),
+    Byte(u8),
+}
+
+/// Characters and escapes between the quotes, the current symbol is the opening quote.
+/// Whitespaces are kept, the iterator stops after the closing quote.
+fn syntax_quoted(it: ParsingIterator, quote: char) -> Result<(ParsingIterator, Vec<TextPiece>), String> {
+    let mut pieces = Vec::new();
+    let mut n = 1;
+    loop {
+        let c = match it.peek(n) {
+            Some(c) if c == quote => break,
+            Some(c) => c,
+            None => return Err(format!("The literal is not closed with {}", quote))
+        };
+        n += 1;
+        if c != '\\' {
+            pieces.push(TextPiece::Char(c));
+            continue;
+        }
+        let escape = it.peek(n).ok_or("Escape sequence is not complete")?;
+        n += 1;
+        let piece = match escape {
+            'n' => TextPiece::Char('\n'),
+            'r' => TextPiece::Char('\r'),
+            't' => TextPiece::Char('\t'),
+            '0' => TextPiece::Char('\0'),
+            '\\' | '\'' | '"' => TextPiece::Char(escape),
+            'x' => {
+                let digits = (n..n + 2).filter_map(|i| it.peek(i)).collect::<String>();
+                n += 2;
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(byte) if digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit()) => TextPiece::Byte(byte),
+                    _ => return Err("\\x must be followed by two hexadecimal digits, like \\x41".to_owned())
+                }
+            }
+            'u' if it.peek(n) == Some('{') => {
+                let digits = (n + 1..).map_while(|i| it.peek(i)).take_while(|c| *c != '}').collect::<String>();
+                n += digits.chars().count() + 2;
+                if !(1..=6).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_hexdigit()) || it.peek(n - 1) != Some('}') {
+                    return Err("\\u must be followed by 1 to 6 hexadecimal digits in braces, like \\u{1F600}".to_owned());
+                }
+                match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+                    Some(c) => TextPiece::Char(c),
+                    None => return Err(format!("\\u{{{}}} is not a Unicode scalar value", digits))
+                }
+            }
+            _ => return Err(format!("Unknown escape sequence \\{}", escape))
+        };
+        pieces.push(piece);
+    }
+    // the closing quote is passed too
+    Ok((it.rewind_n_include_whitespaces(n).rewind_n(1), pieces))
+}
+
+/// A string literal is packed into bytes: characters in UTF-8, escaped bytes as they are. The first byte is
+/// the highest one, as the string is read, the `le` suffix puts it at the lowest byte, as it lies in memory.
+fn syntax_string(it: ParsingIterator) -> Result<(ParsingIterator, RightOperandSource), String> {
+    trace!("syntax_string: {:?}", it.rest());
+    let (it, pieces) = syntax_quoted(it, '"')?;
+    let mut bytes = Vec::new();
+    for piece in pieces {
+        match piece {
+            TextPiece::Char(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            TextPiece::Byte(byte) => bytes.push(byte)
+        }
+    }
+    if bytes.is_empty() {
+        return Err("String literal is empty".to_owned());
+    }
+    let it = match it.current() {
+        Some('l') if it.match_word_from_current("le") => {
+            bytes.reverse();
+            it.rewind_n(2)
+        }
+        Some('b') if it.match_word_from_current("be") => it.rewind_n(2),
+        _ => it
+    };
+    Ok((it, RightOperandSource::DirectSource(BincBuffer::from_bytes(&bytes)?)))
+}
+
 fn syntax_operator(it: ParsingIterator) -> (ParsingIterator, Option<Operator>) {
     trace!("syntax_operator: rest {:?}", it.rest());
     match it.current() {
         Some('u') if it.match_from_current("unsigned") => (it.rewind_n(8), Some(operator_unsigned as Operator)),
+        Some('r') if it.match_from_current("ranges") => (it.rewind_n(6), Some(operator_ranges as Operator)),
         Some('s') if it.match_from_current("signed") => (it.rewind_n(6), Some(operator_signed as Operator)),
+        Some('o') if it.match_from_current("ones") => (it.rewind_n(4), Some(operator_ones_complement as Operator)),
+        Some('s') if it.match_from_current("signmag") => (it.rewind_n(7), Some(operator_sign_magnitude as Operator)),
+        Some('e') if it.match_from_current("excess") => (it.rewind_n(6), Some(operator_excess as Operator)),
+        Some('n') if it.match_from_current("negabinary") => (it.rewind_n(10), Some(operator_negabinary as Operator)),
+        Some('c') if it.match_from_current("codepoint") => (it.rewind_n(9), Some(operator_code_point as Operator)),
+        Some('u') if it.match_from_current("utf8") => (it.rewind_n(4), Some(operator_utf8 as Operator)),
+        Some('u') if it.match_from_current("utf16le") => (it.rewind_n(7), Some(operator_utf16le as Operator)),
+        Some('u') if it.match_from_current("utf16be") => (it.rewind_n(7), Some(operator_utf16be as Operator)),
+        Some('t') if it.match_from_current("tdiv") => (it.rewind_n(4), Some(operator_truncated_division as Operator)),
+        Some('f') if it.match_from_current("fdiv") => (it.rewind_n(4), Some(operator_floored_division as Operator)),
+        Some('w') if it.match_from_current("wrap") => (it.rewind_n(4), Some(operator_wrap as Operator)),
+        Some('s') if it.match_from_current("saturate") => (it.rewind_n(8), Some(operator_saturate as Operator)),
+        Some('c') if it.match_from_current("checked") => (it.rewind_n(7), Some(operator_checked as Operator)),
+        Some('m') if it.match_from_current("modulus") => (it.rewind_n(7), Some(operator_modulus as Operator)),
+        Some('g') if it.match_from_current("gf") => (it.rewind_n(2), Some(operator_field as Operator)),
+        Some('c') if it.match_from_current("crcinit") => (it.rewind_n(7), Some(operator_crc_init as Operator)),
+        Some('c') if it.match_from_current("crcxorout") => (it.rewind_n(9), Some(operator_crc_xorout as Operator)),
+        Some('c') if it.match_from_current("crcrefin") => (it.rewind_n(8), Some(operator_crc_refin as Operator)),
+        Some('c') if it.match_from_current("crcrefout") => (it.rewind_n(9), Some(operator_crc_refout as Operator)),
+        Some('c') if it.match_from_current("crc") => (it.rewind_n(3), Some(operator_crc as Operator)),
+        Some('c') if it.match_from_current("csum") => (it.rewind_n(4), Some(operator_internet_checksum as Operator)),
+        Some('a') if it.match_from_current("adler32") => (it.rewind_n(7), Some(operator_adler32 as Operator)),
         Some('h') if it.match_from_current("help") => (it.rewind_n(4), Some(operator_show_help as Operator)),
         Some('u') if it.match_from_current("undo") => (it.rewind_n(4), Some(operator_undo as Operator)),
         Some('r') if it.match_from_current("redo") => (it.rewind_n(4), Some(operator_redo as Operator)),
         Some('r') if it.match_from_current("root") => (it.rewind_n(4), Some(operator_root as Operator)),
         Some('r') if it.match_from_current("rnd") => (it.rewind_n(3), Some(operator_random as Operator)),
+        Some('b') if it.match_from_current("bswap") => (it.rewind_n(5), Some(operator_byte_swap as Operator)),
+        Some('h') if it.match_from_current("hswap") => (it.rewind_n(5), Some(operator_half_word_swap as Operator)),
+        Some('w') if it.match_from_current("wswap") => (it.rewind_n(5), Some(operator_word_swap as Operator)),
+        Some('b') if it.match_from_current("blsi") => (it.rewind_n(4), Some(operator_isolate_lowest_set_bit as Operator)),
+        Some('b') if it.match_from_current("blsr") => (it.rewind_n(4), Some(operator_reset_lowest_set_bit as Operator)),
+        Some('b') if it.match_from_current("bextr") => (it.rewind_n(5), Some(operator_bitfield_extract as Operator)),
+        Some('b') if it.match_from_current("bfi") => (it.rewind_n(3), Some(operator_bitfield_insert as Operator)),
+        Some('l') if it.match_from_current("lzcnt") => (it.rewind_n(5), Some(operator_leading_zeros as Operator)),
+        Some('t') if it.match_from_current("tzcnt") => (it.rewind_n(5), Some(operator_trailing_zeros as Operator)),
+        Some('p') if it.match_from_current("pdep") => (it.rewind_n(4), Some(operator_deposit_bits as Operator)),
+        Some('p') if it.match_from_current("pext") => (it.rewind_n(4), Some(operator_extract_bits as Operator)),
+        Some('a') if it.match_from_current("adc") => (it.rewind_n(3), Some(operator_add_with_carry as Operator)),
+        Some('s') if it.match_from_current("sbb") => (it.rewind_n(3), Some(operator_subtract_with_borrow as Operator)),
+        Some('r') if it.match_from_current("rcl") => (it.rewind_n(3), Some(operator_rotate_left_through_carry as Operator)),
+        Some('r') if it.match_from_current("rcr") => (it.rewind_n(3), Some(operator_rotate_right_through_carry as Operator)),
+        Some('m') if it.match_from_current("mulw") => (it.rewind_n(4), Some(operator_multiply_wide as Operator)),
+        Some('c') if it.match_from_current("clmul") => (it.rewind_n(5), Some(operator_carryless_multiply as Operator)),
+        Some('d') if it.match_from_current("divmod") => (it.rewind_n(6), Some(operator_divmod as Operator)),
+        Some('g') if it.match_from_current("gcd") => (it.rewind_n(3), Some(operator_gcd as Operator)),
+        Some('l') if it.match_from_current("lcm") => (it.rewind_n(3), Some(operator_lcm as Operator)),
+        Some('m') if it.match_from_current("modinv") => (it.rewind_n(6), Some(operator_modinv as Operator)),
+        Some('m') if it.match_from_current("modpow") => (it.rewind_n(6), Some(operator_modpow as Operator)),
+        Some('p') if it.match_from_current("parity") => (it.rewind_n(6), Some(operator_parity as Operator)),
+        Some('o') if it.match_from_current("oddparity") => (it.rewind_n(9), Some(operator_odd_parity as Operator)),
+        Some('h') if it.match_from_current("hamenc") => (it.rewind_n(6), Some(operator_hamming_encode as Operator)),
+        Some('h') if it.match_from_current("hamdec") => (it.rewind_n(6), Some(operator_hamming_decode as Operator)),
+        Some('s') if it.match_from_current("secenc") => (it.rewind_n(6), Some(operator_secded_encode as Operator)),
+        Some('s') if it.match_from_current("secdec") => (it.rewind_n(6), Some(operator_secded_decode as Operator)),
+        Some('m') if it.match_from_current("mem") => (it.rewind_n(3), Some(operator_memory_order as Operator)),
+        Some('b') if it.match_from_current("bcd") => (it.rewind_n(3), Some(operator_to_bcd as Operator)),
+        Some('u') if it.match_from_current("unbcd") => (it.rewind_n(5), Some(operator_from_bcd as Operator)),
+        Some('g') if it.match_from_current("gray") => (it.rewind_n(4), Some(operator_to_gray as Operator)),
+        Some('u') if it.match_from_current("ungray") => (it.rewind_n(6), Some(operator_from_gray as Operator)),
+        Some('z') if it.match_from_current("zigzag") => (it.rewind_n(6), Some(operator_to_zigzag as Operator)),
+        Some('u') if it.match_from_current("unzigzag") => (it.rewind_n(8), Some(operator_from_zigzag as Operator)),
+        Some('l') if it.match_from_current("leb128") => (it.rewind_n(6), Some(operator_to_leb128 as Operator)),
+        Some('u') if it.match_from_current("unleb128") => (it.rewind_n(8), Some(operator_from_leb128 as Operator)),
+        Some('s') if it.match_from_current("sleb128") => (it.rewind_n(7), Some(operator_to_sleb128 as Operator)),
+        Some('u') if it.match_from_current("unsleb128") => (it.rewind_n(9), Some(operator_from_sleb128 as Operator)),
         Some('s') if it.match_from_current("shf") => (it.rewind_n(3), Some(operator_shuffle as Operator)),
         Some('r') if it.match_from_current("rev") => (it.rewind_n(3), Some(operator_reverse as Operator)),
         Some('c') if it.match_from_current("cnt") => (it.rewind_n(3), Some(operator_count as Operator)),
+        Some('i') if it.match_from_current("isqrt") => (it.rewind_n(5), Some(operator_isqrt as Operator)),
+        Some('i') if it.match_from_current("ilog") => (it.rewind_n(4), Some(operator_ilog as Operator)),
         Some('i') if it.match_from_current("int") => (it.rewind_n(3), Some(operator_int_bits_width as Operator)),
+        Some('f') if it.match_from_current("float") => (it.rewind_n(5), Some(operator_float_bits_width as Operator)),
         Some('p') if it.match_from_current("pow") => (it.rewind_n(3), Some(operator_pow as Operator)),
         Some('~') if it.match_from_current("~>>") => (it.rewind_n(3), Some(operator_unsigned_cyclic_shift_right as Operator)),
         Some('<') if it.match_from_current("<<~") => (it.rewind_n(3), Some(operator_unsigned_cyclic_shift_left as Operator)),
@@ -261,6 +548,17 @@ fn syntax_operator(it: ParsingIterator) -> (ParsingIterator, Option<Operator>) {
         Some('>') if it.match_from_current(">>") => (it.rewind_n(2), Some(operator_signed_shift_right as Operator)),
         Some('<') if it.match_from_current("<<") => (it.rewind_n(2), Some(operator_signed_shift_left as Operator)),
         Some('=') if it.match_from_current("==") => (it.rewind_n(2), Some(operator_equals as Operator)),
+        Some('!') if it.match_from_current("!=") => (it.rewind_n(2), Some(operator_not_equals as Operator)),
+        Some('>') if it.match_from_current(">=") => (it.rewind_n(2), Some(operator_greater_or_equal as Operator)),
+        Some('<') if it.match_from_current("<=") => (it.rewind_n(2), Some(operator_less_or_equal as Operator)),
+        Some('+') if it.match_from_current("+=") => (it.rewind_n(2), Some(operator_sum as Operator)),
+        Some('-') if it.match_from_current("-=") => (it.rewind_n(2), Some(operator_sub as Operator)),
+        Some('*') if it.match_from_current("*=") => (it.rewind_n(2), Some(operator_mul as Operator)),
+        Some('/') if it.match_from_current("/=") => (it.rewind_n(2), Some(operator_div as Operator)),
+        Some('%') if it.match_from_current("%=") => (it.rewind_n(2), Some(operator_mod as Operator)),
+        Some('^') if it.match_from_current("^=") => (it.rewind_n(2), Some(operator_xor as Operator)),
+        Some('&') if it.match_from_current("&=") => (it.rewind_n(2), Some(operator_and as Operator)),
+        Some('|') if it.match_from_current("|=") => (it.rewind_n(2), Some(operator_or as Operator)),
         Some('?') => (it.rewind_n(1), Some(operator_show_help as Operator)),
         Some('=') => (it.rewind_n(1), Some(operator_assign as Operator)),
         Some('+') => (it.rewind_n(1), Some(operator_sum as Operator)),
@@ -279,20 +577,177 @@ fn syntax_operator(it: ParsingIterator) -> (ParsingIterator, Option<Operator>) {
     }
 }
 
-fn syntax_negative_number(it: ParsingIterator) -> Result<(ParsingIterator, RightOperandSource), String> {
+/// Name of a floating point format: bfloat16 or a minifloat eXmY, like e4m3 or e5m2, eXmYfn has no infinity.
+fn syntax_float_format(it: ParsingIterator) -> Result<(ParsingIterator, Option<FloatFormat>), String> {
+    trace!("syntax_float_format: {:?}", it.current());
+    match it.current() {
+        Some('b') if it.match_word_from_current("bfloat16") => Ok((it.rewind_n(8), Some(FloatFormat::BFLOAT16))),
+        Some('e') if it.peek(1).is_some_and(|c| c.is_ascii_digit()) => {
+            let (it, exponent_bits) = syntax_index(it.rewind_n(1));
+            if it.current() != Some('m') {
+                return Err("Minifloat format needs the number of mantissa bits, like e4m3".to_owned());
+            }
+            let (it, fraction_bits) = match syntax_index(it.rewind_n(1)) {
+                (it, Some(fraction_bits)) => (it, fraction_bits),
+                (_, None) => return Err("Minifloat format needs the number of mantissa bits, like e4m3".to_owned())
+            };
+            let (it, finite) = if it.match_word_from_current("fn") { (it.rewind_n(2), true) } else { (it, false) };
+            let format = match (exponent_bits.unwrap_or(0), fraction_bits) {
+                // FP8 E4M3 has no infinity, as OCP defines it
+                (4, 3) => FloatFormat::E4M3,
+                (exponent_bits, fraction_bits) => FloatFormat::new(exponent_bits, fraction_bits, finite)?
+            };
+            Ok((it, Some(format)))
+        }
+        _ => Ok((it, None))
+    }
+}
+
+/// Q notation of a fixed point format, like q15, q7.8 or uq16.16, or a rounding mode of the current fixed point format.
+fn syntax_fixed_format(it: ParsingIterator) -> Result<(ParsingIterator, Option<FixedFormat>), String> {
+    trace!("syntax_fixed_format: {:?}", it.current());
+    let rounding = match it.current() {
+        Some('r') if it.match_word_from_current("rne") => Some(Rounding::NearestEven),
+        Some('r') if it.match_word_from_current("rna") => Some(Rounding::NearestAway),
+        Some('r') if it.match_word_from_current("rtz") => Some(Rounding::TowardZero),
+        Some('r') if it.match_word_from_current("rdn") => Some(Rounding::Down),
+        Some('r') if it.match_word_from_current("rup") => Some(Rounding::Up),
+        _ => None
+    };
+    let current_format = match it.number_type {
+        BincBufferType::Fixed(format) => Some(format),
+        _ => None
+    };
+    if let Some(rounding) = rounding {
+        return match current_format {
+            Some(format) => Ok((it.rewind_n(3), Some(FixedFormat { rounding, ..format }))),
+            None => Err("Rounding mode can be set only for a fixed point number, like q7.8".to_owned())
+        };
+    }
+
+    let (it, signed) = match it.current() {
+        Some('q') => (it.rewind_n(1), true),
+        Some('u') if it.match_from_current("uq") => (it.rewind_n(2), false),
+        _ => return Ok((it, None))
+    };
+    let (it, integer_bits, fraction_bits) = match syntax_index(it) {
+        (it, Some(first)) if it.current() == Some('.') => match syntax_index(it.rewind_n(1)) {
+            (it, Some(second)) => (it, first, second),
+            (_, None) => return Err("Q notation needs the number of fraction bits after '.', like q7.8".to_owned())
+        },
+        (it, Some(first)) => (it, 0, first),
+        (_, None) => return Err("Q notation needs the number of bits, like q15, q7.8 or uq16.16".to_owned())
+    };
+    let format = FixedFormat::new(signed, integer_bits, fraction_bits);
+    if format.width() == 0 {
+        return Err("Fixed point number must have at least one bit".to_owned());
+    }
+    // the rounding mode is kept, when the format changes
+    match current_format {
+        Some(current_format) => Ok((it, Some(FixedFormat { rounding: current_format.rounding, ..format }))),
+        None => Ok((it, Some(format)))
+    }
+}
+
+/// A CRC preset, like crc32, crc32c or crc16ccitt, or a custom CRC of the width and the polynomial, like crc16 0x1021.
+fn syntax_crc_model(it: ParsingIterator) -> Result<(ParsingIterator, Option<CrcModel>), String> {
+    trace!("syntax_crc_model: {:?}", it.current());
+    if !(it.match_from_current("crc") && it.peek(3).is_some_and(|c| c.is_ascii_digit())) {
+        return Ok((it, None));
+    }
+    // the width is not followed by whitespaces, the polynomial may be: crc16 0x1021
+    let digits = (3..).map_while(|i| it.peek(i).filter(|c| c.is_ascii_digit())).collect::<String>();
+    let width = digits.parse::<usize>().unwrap_or(0);
+    let it = it.rewind_n(3 + digits.len());
+    let presets = [
+        (16, "ccitt", CrcModel::CRC16_IBM_3740),
+        (16, "kermit", CrcModel::CRC16_KERMIT),
+        (16, "xmodem", CrcModel::CRC16_XMODEM),
+        (32, "c", CrcModel::CRC32_ISCSI),
+    ];
+    for (preset_width, suffix, model) in presets {
+        if width == preset_width && it.match_word_from_current(suffix) {
+            return Ok((it.rewind_n(suffix.len()), Some(model)));
+        }
+    }
+    match syntax_rvalue(it)? {
+        (it, RightOperandSource::Empty) => match width {
+            8 => Ok((it, Some(CrcModel::CRC8_SMBUS))),
+            16 => Ok((it, Some(CrcModel::CRC16_ARC))),
+            32 => Ok((it, Some(CrcModel::CRC32_ISO_HDLC))),
+            64 => Ok((it, Some(CrcModel::CRC64_XZ))),
+            _ => Err(format!("There is no CRC-{} preset, give the polynomial, like crc{} 0x1021", width, width))
+        },
+        (_, RightOperandSource::DirectSource(poly)) if poly.is_negative() => Err("The polynomial must be a positive number".to_owned()),
+        (it, RightOperandSource::DirectSource(poly)) => {
+            let poly = poly.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)).to_u64().unwrap_or(u64::MAX);
+            Ok((it, Some(CrcModel::custom(width, poly)?)))
+        }
+        _ => Err(format!("CRC-{} needs a number as its polynomial, like crc{} 0x1021", width, width))
+    }
+}
+
+fn syntax_signed_number(it: ParsingIterator, is_negative: bool) -> Result<(ParsingIterator, RightOperandSource), String> {
     match it.current() {
         Some(c) => match c {
-            '1'..='9' => syntax_number(it, 10, true),
-            '0' => syntax_radix_number(it.rewind_n(1), true),
-            _ => Err("Bad number syntax".to_owned())
+            '0' if it.match_from_current("0.") => syntax_number(it, 10, is_negative),
+            '1'..='9' => syntax_number(it, 10, is_negative),
+            '0' => syntax_radix_number(it.rewind_n(1), is_negative),
+            _ => syntax_float_name(it, is_negative)
         }
-        None => Err("Bad negative number syntax".to_owned())
+        None => Err("Bad signed number syntax".to_owned())
     }
 }
 
+fn syntax_float_name(it: ParsingIterator, is_negative: bool) -> Result<(ParsingIterator, RightOperandSource), String> {
+    let (it, literal) = match it.current() {
+        Some('i') if it.match_from_current("inf") => (it.rewind_n(3), FloatLiteral::Infinity { negative: is_negative }),
+        Some('e') if it.match_from_current("eps") => (it.rewind_n(3), FloatLiteral::Epsilon { negative: is_negative }),
+        Some('N') if it.match_from_current("NaN") => syntax_nan_payload(it.rewind_n(3), is_negative, true)?,
+        Some('n') if it.match_from_current("nan") => syntax_nan_payload(it.rewind_n(3), is_negative, true)?,
+        Some('s') if it.match_from_current("sNaN") => syntax_nan_payload(it.rewind_n(4), is_negative, false)?,
+        _ => return Err(format!("number or range had been expected, but '{}' was found", String::from_iter(it.rest())))
+    };
+    syntax_float_literal(it, literal)
+}
+
+fn syntax_nan_payload(it: ParsingIterator, is_negative: bool, is_quiet: bool) -> Result<(ParsingIterator, FloatLiteral), String> {
+    if it.current() != Some('(') {
+        return Ok((it, FloatLiteral::NaN { negative: is_negative, quiet: is_quiet, payload: 0 }));
+    }
+    let number_type = it.number_type;
+    let it = it.rewind_n(1);
+    // a bare 0 is the payload, not a radix prefix
+    let (it, payload) = if it.match_from_current("0)") { (it.rewind_n(1), 0) } else {
+        match syntax_rvalue(it.with_number_type(BincBufferType::Integer))? {
+            (it, RightOperandSource::DirectSource(number)) if !number.is_negative() && number.max_size() <= 64 =>
+                (it.with_number_type(number_type), number.to_usize() as u64),
+            _ => return Err("NaN payload must be a non-negative number of at most 64 bits".to_owned())
+        }
+    };
+    match it.current() {
+        Some(')') => Ok((it.rewind_n(1), FloatLiteral::NaN { negative: is_negative, quiet: is_quiet, payload })),
+        _ => Err("NaN payload must be closed with ')'".to_owned())
+    }
+}
+
+fn syntax_float_literal(it: ParsingIterator, literal: FloatLiteral) -> Result<(ParsingIterator, RightOperandSource), String> {
+    trace!("syntax_float_literal: {:?}", literal);
+    let number = match it.number_type {
+        BincBufferType::Float(format) => BincBuffer::from_float(literal.encode(format)?, format)?,
+        BincBufferType::Fixed(format) => BincBuffer::from_fixed(format.quantize(&literal)?, format)?,
+        BincBufferType::Integer => BincBuffer::from_float_literal(&literal)?
+    };
+    Ok((it, RightOperandSource::DirectSource(number)))
+}
+
 // TODO refactor number parsing
 fn syntax_rvalue(it: ParsingIterator) -> Result<(ParsingIterator, RightOperandSource), String> {
     trace!("syntax_rvalue: with current symbol '{:?}'", it.current());
+    let it = match syntax_named_access(it) {
+        (it, Some(named_access)) => return Ok((it, RightOperandSource::NamedAccessSource(named_access))),
+        (it, None) => it
+    };
     match it.current() {
         Some(c) => match c {
             '[' => match syntax_accessor(it) {
@@ -301,10 +756,16 @@ fn syntax_rvalue(it: ParsingIterator) -> Result<(ParsingIterator, RightOperandSo
                 Err(message) => Err(message)
             },
             '1'..='9' => syntax_number(it, 10, false),
+            '0' if it.match_from_current("0.") => syntax_number(it, 10, false),
             '0' => syntax_radix_number(it.rewind_n(1), false),
-            '-' => syntax_negative_number(it.rewind_n(1)),
+            '-' => syntax_signed_number(it.rewind_n(1), true),
+            '+' => syntax_signed_number(it.rewind_n(1), false),
             '\'' => syntax_letter(it),
-            _ => Err(format!("number or range had been expected, but '{}' was found", String::from_iter(it.rest())).to_owned())
+            '"' => syntax_string(it),
+            _ => match syntax_range_name(it) {
+                (it, Some(name)) => Ok((it, RightOperandSource::NamedAccessSource(NamedAccess::Range(name)))),
+                (it, None) => syntax_float_name(it, false)
+            }
         }
         None => Ok((it, RightOperandSource::Empty))
     }
@@ -334,6 +795,7 @@ fn syntax_radix_number(it: ParsingIterator, is_negative: bool) -> Result<(Parsin
                 }
             _ => Err(format!("Bad radix letter '{}'", c))
         },
+        None if is_negative && matches!(it.number_type, BincBufferType::Float(_)) => syntax_float_literal(it, FloatLiteral::parse("-0")?),
         None => Ok((it, RightOperandSource::DirectSource(BincBuffer::from_str("0", 10).unwrap())))
     }
 }
@@ -344,8 +806,35 @@ fn syntax_number(mut it: ParsingIterator, radix: u32, is_negative: bool) -> Resu
     if is_negative {
         number_literal.push('-')
     }
+    if radix == 16 {
+        number_literal.push_str("0x")
+    }
+    let exponent_letters: &[char] = match radix {
+        10 => &['e', 'E'],
+        16 => &['p', 'P'],
+        _ => &[]
+    };
+    let mut is_float = false;
     while let Some(c) = it.current() {
         match c {
+            '.' if !is_float && !exponent_letters.is_empty() => {
+                is_float = true;
+                number_literal.push(c)
+            },
+            _ if exponent_letters.contains(&c) => {
+                is_float = true;
+                number_literal.push(c);
+                it.next();
+                if let Some(sign @ ('+' | '-')) = it.current() {
+                    number_literal.push(sign);
+                    it.next();
+                }
+                while let Some(digit @ '0'..='9') = it.current() {
+                    number_literal.push(digit);
+                    it.next();
+                }
+                break
+            },
             '0'..='9' => number_literal.push(c),
             'a'..='f' | 'A'..='F' => number_literal.push(c),
             _ => break
@@ -353,22 +842,103 @@ fn syntax_number(mut it: ParsingIterator, radix: u32, is_negative: bool) -> Resu
         it.next();
     }
     trace!("syntax_number: number literal '{}'", number_literal);
-    match BincBuffer::from_str(&number_literal, radix) {
+    if is_float {
+        return syntax_float_literal(it, FloatLiteral::parse(&number_literal)?);
+    }
+    match BincBuffer::from_str(&number_literal.replacen("0x", "", 1), radix) {
         Ok(number) => Ok((it, RightOperandSource::DirectSource(number))),
         Err(message) => Err(message)
     }
 }
 
-pub fn parse(cmd: &str) -> Result<(LeftOperandSource, Operator, RightOperandSource), String> {
+/// The whole command is a name of a number format, the buffer takes the format of the empty number.
+fn syntax_format_command(it: ParsingIterator, format_carrier: BincBuffer) -> Result<(LeftOperandSource, Operator, RightOperandSource), String> {
+    if it.current().is_some() {
+        return Err(format!("Could not parse all symbols in command, left '{}'", String::from_iter(it.rest())))
+    }
+    let whole_buffer = LeftOperandSource::RangeSource(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
+    Ok((whole_buffer, operator_number_format as Operator, RightOperandSource::DirectSource(format_carrier)))
+}
+
+/// The whole command is a CRC model, which the buffer keeps for `crc` command.
+fn syntax_crc_command(it: ParsingIterator, model: CrcModel) -> Result<(LeftOperandSource, Operator, RightOperandSource), String> {
+    if it.current().is_some() {
+        return Err(format!("Could not parse all symbols in command, left '{}'", String::from_iter(it.rest())))
+    }
+    let mut model_carrier = BincBuffer::new(BincBufferType::Integer, false, 8)?;
+    model_carrier.set_crc_model(model);
+    let whole_buffer = LeftOperandSource::RangeSource(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit));
+    Ok((whole_buffer, operator_crc_model as Operator, RightOperandSource::DirectSource(model_carrier)))
+}
+
+/// `let name = [i:j]` names a range, the range can be another name; `let name` forgets it.
+fn syntax_let_command(it: ParsingIterator) -> Result<(LeftOperandSource, Operator, RightOperandSource), String> {
+    let (it, name) = match syntax_range_name(it) {
+        (it, Some(name)) if is_range_name(&name) => (it, name),
+        _ => return Err("A range is named with a word, which is not a keyword, like `let opcode = [31:26]`".to_owned())
+    };
+    let (it, range) = match it.current() {
+        Some('=') => syntax_rvalue(it.rewind_n(1))?,
+        _ => (it, RightOperandSource::Empty)
+    };
+    if it.current().is_some() {
+        return Err(format!("Could not parse all symbols in command, left '{}'", String::from_iter(it.rest())))
+    }
+    Ok((LeftOperandSource::NamedAccessSource(NamedAccess::NewRange(name)), operator_let as Operator, range))
+}
+
+/// The name is read back as the same range, either as the target or as the operand.
+fn is_range_name(name: &str) -> bool {
+    let is_target = matches!(
+        parse(&format!("{}=0", name), BincBufferType::Integer),
+        Ok((LeftOperandSource::NamedAccessSource(NamedAccess::Range(target)), _, _)) if target == name
+    );
+    let is_operand = matches!(
+        parse(&format!("={}", name), BincBufferType::Integer),
+        Ok((_, _, RightOperandSource::NamedAccessSource(NamedAccess::Range(operand)))) if operand == name
+    );
+    is_target && is_operand
+}
+
+pub fn parse(cmd: &str, number_type: BincBufferType) -> Result<(LeftOperandSource, Operator, RightOperandSource), String> {
     trace!("parse: command '{}'", cmd);
-    let (it_after_first_operand, left_operand_source) = match syntax_accessor(
-        match ParsingIterator::from(&cmd) {
-            Err(msg) => return Err(format!("Cannot create parser for command '{}': ", cmd) + msg),
-            Ok(it) => it
+    let it = match ParsingIterator::from(cmd) {
+        Err(msg) => return Err(format!("Cannot create parser for command '{}': ", cmd) + msg),
+        Ok(it) => it.with_number_type(number_type)
+    };
+    if it.match_word_from_current("let") {
+        return syntax_let_command(it.rewind_n(3));
+    }
+    // a format is a whole command, a word followed by an operator is a range, like `q1 = 1`
+    let is_range_target = range_name(&it).is_some_and(|name| {
+        let after_name = String::from_iter(it.rest().iter().skip(name.chars().count()));
+        ParsingIterator::from(&after_name).is_ok_and(|after_name| syntax_operator(after_name).1.is_some())
+    });
+    let it = if is_range_target { it } else {
+        match syntax_float_format(it)? {
+            (it, Some(format)) => return syntax_format_command(it, BincBuffer::from_float(0, format)?),
+            (it, None) => it
+        }
+    };
+    let it = if is_range_target { it } else {
+        match syntax_fixed_format(it)? {
+            (it, Some(format)) => return syntax_format_command(it, BincBuffer::from_fixed(BufferType::default(), format)?),
+            (it, None) => it
         }
-    ) {
+    };
+    let it = match syntax_crc_model(it)? {
+        (it, Some(model)) => return syntax_crc_command(it, model),
+        (it, None) => it
+    };
+    let (it_after_first_operand, left_operand_source) = match syntax_accessor(it) {
         Ok((it, Some(ops))) => (it, LeftOperandSource::RangeSource(ops)),
-        Ok((it, None)) => (it, LeftOperandSource::RangeSource(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit))),
+        Ok((it, None)) => match syntax_named_access(it) {
+            (it, Some(named_access)) => (it, LeftOperandSource::NamedAccessSource(named_access)),
+            (it, None) => match syntax_range_name(it) {
+                (it, Some(name)) => (it, LeftOperandSource::NamedAccessSource(NamedAccess::Range(name))),
+                (it, None) => (it, LeftOperandSource::RangeSource(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)))
+            }
+        },
         Err(message) => return Err(message)
     };
     let (it_after_operator, operator_handler) = match syntax_operator(it_after_first_operand) {
@@ -380,7 +950,7 @@ pub fn parse(cmd: &str) -> Result<(LeftOperandSource, Operator, RightOperandSour
         Err(message) => return Err(message)
     };
     trace!("parse: resulting operands {:?} {:?}", left_operand_source, right_operand_source);
-    if it_after_second_operand.current() != None {
+    if it_after_second_operand.current().is_some() {
         return Err(format!("Could not parse all symbols in command, left '{}'", String::from_iter(it_after_second_operand.rest())).to_owned())
     }
     Ok((left_operand_source, operator_handler, right_operand_source))
@@ -484,9 +1054,9 @@ fn parsing_iterator_skip_whitespaces() {
     let mut it = ParsingIterator::from(" \t[ 0 ]   =  1").unwrap();
     let pat = "[0]=1".as_bytes();
     assert_eq!(Some(*pat.first().unwrap() as char), it.current());
-    for i in 1..pat.len() {
+    for &c in pat.iter().skip(1) {
         it = it.rewind_n(1);
-        assert_eq!(Some(pat[i] as char), it.current());
+        assert_eq!(Some(c as char), it.current());
     }
 }
 
@@ -494,16 +1064,16 @@ fn parsing_iterator_skip_whitespaces() {
 fn syntax_index_test() {
     match syntax_index(ParsingIterator::from("").unwrap()) {
         (_, Some(_)) => panic!("syntax_index() must return no value if source string was empty"),
-        (it, None) if it.current() == None => (), // success
+        (it, None) if it.current().is_none() => (), // success
         _ => panic!("syntax_index() must exhaust iterator with empty string")
     }
     match syntax_index(ParsingIterator::from("0").unwrap()) {
-        (it, Some(parsed)) if it.current() == None => assert_eq!(0, parsed),
+        (it, Some(parsed)) if it.current().is_none() => assert_eq!(0, parsed),
         (_, None) => panic!("syntax_index() must parse 0"),
         _ => panic!("syntax_index() must exhaust iterator with string containing one number")
     }
     match syntax_index(ParsingIterator::from(&usize::MAX.to_string()).unwrap()) {
-        (it, Some(parsed)) if it.current() == None => assert_eq!(usize::MAX, parsed),
+        (it, Some(parsed)) if it.current().is_none() => assert_eq!(usize::MAX, parsed),
         (_, None) => panic!("syntax_index() must parse usize::MAX"),
         _ => panic!("syntax_index() must exhaust iterator with empty string")
     }
@@ -512,7 +1082,7 @@ fn syntax_index_test() {
         (_, Some(_)) => panic!("syntax_index() must return no value if source string has no leading digits"),
         (mut it, None) => {
             let mut count = 0;
-            while let Some(_) = it.next() {
+            while it.next().is_some() {
                 count += 1;
             }
             assert_eq!(test_string.len() - 1, count);
@@ -523,7 +1093,7 @@ fn syntax_index_test() {
             assert_eq!(123, parsed);
             assert_eq!(Some('a'), it.current());
             let mut count = 0;
-            while let Some(_) = it.next() {
+            while it.next().is_some() {
                 count += 1;
             }
             assert_eq!("adf".len() - 1, count);
@@ -536,12 +1106,12 @@ fn syntax_index_test() {
 fn syntax_letter_test() {
     match syntax_letter(ParsingIterator::from("'a'").unwrap()) {
         Ok((it, right_operand_source)) => {
-            if let Some(_) = it.current() {
+            if it.current().is_some() {
                 panic!("syntax_letter() did not parse the whole string to the end")
             }
             match right_operand_source {
                 RightOperandSource::DirectSource(n) => {
-                    assert_eq!(0b01100001u128, n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)))
+                    assert_eq!(BufferType::from(0b01100001u128), n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit)))
                 }
                 _ => panic!("syntax_letter() returned not a DirectSource")
             }
@@ -549,20 +1119,36 @@ fn syntax_letter_test() {
         Err(_) => panic!("syntax_letter() failed to parse letter literal")
     }
 
-    match syntax_letter(ParsingIterator::from("'aa'").unwrap()) {
-        Ok((_, _)) => {
-            panic!("syntax_letter() failed to detect that there are several chars")
-        }
-        Err(_) => {} // OK
+    if syntax_letter(ParsingIterator::from("'aa'").unwrap()).is_ok() {
+        panic!("syntax_letter() failed to detect that there are several chars")
     }
 
-    match syntax_letter(ParsingIterator::from("''").unwrap()) {
-        Ok((_, _)) => {
-            panic!("syntax_letter() failed to detect that there are no chars")
-        }
-        Err(_) => {} // OK
+    if syntax_letter(ParsingIterator::from("''").unwrap()).is_ok() {
+        panic!("syntax_letter() failed to detect that there are no chars")
     }
 
+    let letter = |literal: &str| match syntax_letter(ParsingIterator::from(literal).unwrap()) {
+        Ok((_, RightOperandSource::DirectSource(n))) => Ok(n.get_bits(BitsIndexRange(BitIndex::HighestBit, BitIndex::LowestBit))),
+        Ok(_) => Err("not a DirectSource".to_owned()),
+        Err(message) => Err(message)
+    };
+    assert_eq!(Ok(BufferType::from(0x3bbu32)), letter("'\\u{3BB}'"));
+    assert_eq!(Ok(BufferType::from(0x1f600u32)), letter("'\\u{1F600}'"));
+    assert_eq!(Ok(BufferType::from(0x1f600u32)), letter("'😀'"));
+    assert_eq!(Ok(BufferType::from(0x41u32)), letter("'\\x41'"));
+    assert_eq!(Ok(BufferType::from(0x0au32)), letter("'\\n'"));
+    assert_eq!(Ok(BufferType::from(0x09u32)), letter("'\\t'"));
+    assert_eq!(Ok(BufferType::from(0u32)), letter("'\\0'"));
+    assert_eq!(Ok(BufferType::from(0x27u32)), letter("'\\''"));
+    assert_eq!(Ok(BufferType::from(0x27u32)), letter("'''"));
+    assert_eq!(Ok(BufferType::from(0x5cu32)), letter("'\\\\'"));
+    assert_eq!(
+        Err("'👍🏽' is 2 code points U+1F44D U+1F3FD, a letter literal takes one, a string literal in double quotes takes several".to_owned()),
+        letter("'👍🏽'")
+    );
+    assert_eq!(Err("Unknown escape sequence \\q".to_owned()), letter("'\\q'"));
+    assert_eq!(Err("The literal is not closed with '".to_owned()), letter("'\\u{41}"));
+
     match syntax_letter(ParsingIterator::from("  ' '  ").unwrap()) {
         Ok((_, RightOperandSource::DirectSource(number))) => {
             if !number.to_string_as_char().eq("' '") {
@@ -622,7 +1208,7 @@ fn syntax_accessor_test() {
 
     match syntax_accessor(ParsingIterator::from("[3:]").unwrap()) {
         Ok((_, Some(BitsIndexRange(BitIndex::IndexedBit(left), BitIndex::LowestBit)))) => {
-            if !(left == 3) {
+            if left != 3 {
                 panic!("syntax_accessor() parses wrong range")
             }
         }
@@ -632,7 +1218,7 @@ fn syntax_accessor_test() {
 
     match syntax_accessor(ParsingIterator::from("[:5]").unwrap()) {
         Ok((_, Some(BitsIndexRange(BitIndex::HighestBit, BitIndex::IndexedBit(right))))) => {
-            if !(right == 5) {
+            if right != 5 {
                 panic!("syntax_accessor() parses wrong range")
             }
         }
@@ -640,3 +1226,28 @@ fn syntax_accessor_test() {
         Err(_) => panic!("syntax_accessor() cannot parse")
     }
 }
+
+#[test]
+fn syntax_named_access_test() {
+    match parse("e+=1", BincBufferType::Integer) {
+        Ok((LeftOperandSource::NamedAccessSource(NamedAccess::Exponent), _, RightOperandSource::DirectSource(n))) => assert_eq!(1, n.to_usize()),
+        _ => panic!("parse() cannot parse exponent named access")
+    }
+    match parse("[7:0]=f", BincBufferType::Integer) {
+        Ok((LeftOperandSource::RangeSource(_), _, RightOperandSource::NamedAccessSource(NamedAccess::Fraction))) => (),
+        _ => panic!("parse() cannot parse fraction named access")
+    }
+    match parse("s", BincBufferType::Integer) {
+        Ok((LeftOperandSource::NamedAccessSource(NamedAccess::Sign), _, RightOperandSource::Empty)) => (),
+        _ => panic!("parse() cannot parse sign named access")
+    }
+    // keywords which start with the same letters are not named accesses
+    match parse("float32", BincBufferType::Integer) {
+        Ok((LeftOperandSource::RangeSource(_), _, RightOperandSource::DirectSource(n))) => assert_eq!(32, n.to_usize()),
+        _ => panic!("parse() cannot parse float32")
+    }
+    match parse("=eps", BincBufferType::Float(FloatFormat::BINARY32)) {
+        Ok((_, _, RightOperandSource::DirectSource(n))) => assert_eq!(0x3400_0000, n.to_usize()),
+        _ => panic!("parse() cannot parse eps")
+    }
+}
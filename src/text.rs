@@ -0,0 +1,155 @@
+// binc
+// Copyright (C) 2022  Artem Ayrapetov
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+/// How an integer is read as text: a Unicode scalar value, or bytes of UTF-8 or UTF-16,
+/// which go from the highest byte of the number, as it is written.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TextEncoding {
+    CodePoint,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::CodePoint => "codepoint",
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+        }
+    }
+
+    /// Bytes of the number without leading zero bytes, UTF-16 keeps whole code units.
+    pub fn bytes(&self, value: &BigUint) -> Vec<u8> {
+        let mut bytes = value.to_bytes_be();
+        if matches!(self, TextEncoding::Utf16Le | TextEncoding::Utf16Be) && bytes.len() % 2 == 1 {
+            bytes.insert(0, 0);
+        }
+        bytes
+    }
+
+    /// Characters of the number, or what makes it an invalid encoding.
+    pub fn decode(&self, value: &BigUint) -> Result<String, String> {
+        let bytes = self.bytes(value);
+        match self {
+            TextEncoding::CodePoint => code_point(value).map(String::from),
+            TextEncoding::Utf8 => decode_utf8(&bytes),
+            TextEncoding::Utf16Le => decode_utf16(bytes.chunks(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]]))),
+            TextEncoding::Utf16Be => decode_utf16(bytes.chunks(2).map(|unit| u16::from_be_bytes([unit[0], unit[1]]))),
+        }
+    }
+
+    /// Byte pattern and characters, for the UI: `UTF-8 e5 bf 83  "心"`, a code point shows its UTF-8 bytes.
+    pub fn describe(&self, value: &BigUint) -> String {
+        let (pattern, text) = match self {
+            TextEncoding::CodePoint => match code_point(value) {
+                Ok(c) => (format!("U+{:04X}  UTF-8 {}", c as u32, hex_bytes(c.to_string().as_bytes())), Ok(c.to_string())),
+                Err(e) => (format!("0x{:x}", value), Err(e))
+            },
+            _ => (hex_bytes(&self.bytes(value)), self.decode(value))
+        };
+        match text {
+            Ok(text) => format!("{} {}  {:?}", self.name(), pattern, text),
+            Err(e) => format!("{} {}  {}", self.name(), pattern, e)
+        }
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" ")
+}
+
+fn code_point(value: &BigUint) -> Result<char, String> {
+    match value.to_u32() {
+        Some(scalar @ 0xd800..=0xdfff) => Err(format!("U+{:04X} is a surrogate, not a character", scalar)),
+        Some(scalar) if scalar <= 0x10ffff => Ok(char::from_u32(scalar).unwrap_or_default()),
+        _ => Err(format!("0x{:x} is beyond U+10FFFF", value))
+    }
+}
+
+/// UTF-8 decoder, which tells overlong sequences and encoded surrogates apart from other errors.
+fn decode_utf8(bytes: &[u8]) -> Result<String, String> {
+    let mut text = String::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i];
+        let (length, lowest, bits) = match lead {
+            0x00..=0x7f => (1, 0, lead as u32),
+            0xc0..=0xdf => (2, 0x80, lead as u32 & 0x1f),
+            0xe0..=0xef => (3, 0x800, lead as u32 & 0x0f),
+            0xf0..=0xf7 => (4, 0x10000, lead as u32 & 0x07),
+            0x80..=0xbf => return Err(format!("byte {} 0x{:02x} is a continuation byte without a leading one", i, lead)),
+            _ => return Err(format!("byte {} 0x{:02x} never appears in UTF-8", i, lead))
+        };
+        if i + length > bytes.len() {
+            return Err(format!("the sequence at byte {} is cut short, it takes {} bytes", i, length));
+        }
+        let mut scalar = bits;
+        for (j, &byte) in bytes[i + 1..i + length].iter().enumerate() {
+            if byte & 0xc0 != 0x80 {
+                return Err(format!("byte {} 0x{:02x} is not a continuation byte", i + 1 + j, byte));
+            }
+            scalar = scalar << 6 | (byte & 0x3f) as u32;
+        }
+        if scalar < lowest {
+            return Err(format!("bytes {}..{} are an overlong encoding of U+{:04X}", i, i + length - 1, scalar));
+        }
+        match scalar {
+            0xd800..=0xdfff => return Err(format!("bytes {}..{} encode the surrogate U+{:04X}", i, i + length - 1, scalar)),
+            0x110000.. => return Err(format!("bytes {}..{} encode 0x{:x}, which is beyond U+10FFFF", i, i + length - 1, scalar)),
+            _ => text.push(char::from_u32(scalar).unwrap_or_default())
+        }
+        i += length;
+    }
+    Ok(text)
+}
+
+fn decode_utf16(units: impl Iterator<Item=u16>) -> Result<String, String> {
+    let mut text = String::new();
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok(c) => text.push(c),
+            Err(e) => return Err(format!(
+                "unit {} 0x{:04x} is an unpaired surrogate", text.encode_utf16().count(), e.unpaired_surrogate()
+            ))
+        }
+    }
+    Ok(text)
+}
+
+#[test]
+fn text_encodings() {
+    let decode = |encoding: TextEncoding, value: u64| encoding.decode(&BigUint::from(value));
+    assert_eq!(Ok("心".to_owned()), decode(TextEncoding::Utf8, 0xe5bf83));
+    assert_eq!(Ok("😀".to_owned()), decode(TextEncoding::Utf8, 0xf09f9880));
+    assert_eq!(Ok("😀".to_owned()), decode(TextEncoding::Utf16Be, 0xd83dde00));
+    assert_eq!(Ok("😀".to_owned()), decode(TextEncoding::Utf16Le, 0x3dd800de));
+    assert_eq!(Ok("A".to_owned()), decode(TextEncoding::Utf16Be, 0x41));
+    assert_eq!(Ok("λ".to_owned()), decode(TextEncoding::CodePoint, 0x3bb));
+    assert_eq!(Err("bytes 0..1 are an overlong encoding of U+0000".to_owned()), decode(TextEncoding::Utf8, 0xc080));
+    assert_eq!(Err("bytes 0..2 encode the surrogate U+D800".to_owned()), decode(TextEncoding::Utf8, 0xeda080));
+    assert_eq!(Err("the sequence at byte 1 is cut short, it takes 3 bytes".to_owned()), decode(TextEncoding::Utf8, 0x41e5bf));
+    assert_eq!(Err("byte 0 0x80 is a continuation byte without a leading one".to_owned()), decode(TextEncoding::Utf8, 0x80));
+    assert_eq!(Err("unit 1 0xdc00 is an unpaired surrogate".to_owned()), decode(TextEncoding::Utf16Be, 0x0041dc00));
+    assert_eq!(Err("unit 2 0xd83d is an unpaired surrogate".to_owned()), decode(TextEncoding::Utf16Be, 0xd83dde00d83d));
+    assert_eq!(Err("U+DFFF is a surrogate, not a character".to_owned()), decode(TextEncoding::CodePoint, 0xdfff));
+    assert_eq!(Err("0x110000 is beyond U+10FFFF".to_owned()), decode(TextEncoding::CodePoint, 0x110000));
+}